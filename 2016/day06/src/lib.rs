@@ -0,0 +1,49 @@
+use aoc_common::{Result, Solution};
+use std::collections::HashMap;
+
+
+fn count_columns(lines: &[String]) -> Vec<HashMap<char, usize>> {
+  let mut counters: Vec<HashMap<char, usize>>  = Vec::new();
+  for s in lines {
+    for (i, ch) in s.chars().enumerate() {
+      if counters.len() <= i {
+        counters.push(HashMap::new());
+      }
+      let hm = &mut counters[i];
+      *hm.entry(ch).or_insert(0) += 1;
+    }
+  }
+  counters
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+  type Input = Vec<String>;
+
+  fn parse(data: &str) -> Result<Vec<String>> {
+    Ok(data.lines().map(ToOwned::to_owned).collect())
+  }
+
+  fn part1(lines: &Vec<String>) -> Result<String> {
+    let mut s = String::new();
+    for hm in count_columns(lines) {
+      let max = hm.iter()
+                  .fold((&'~', &usize::MIN),
+                        |acc, kv| if kv.1 > acc.1 { kv } else { acc });
+      s.push(*max.0);
+    }
+    Ok(s)
+  }
+
+  fn part2(lines: &Vec<String>) -> Result<String> {
+    let mut s = String::new();
+    for hm in count_columns(lines) {
+      let min = hm.iter()
+                  .fold((&'~', &usize::MAX),
+                        |acc, kv| if kv.1 < acc.1 { kv } else { acc });
+      s.push(*min.0);
+    }
+    Ok(s)
+  }
+}
@@ -0,0 +1,71 @@
+use aoc_common::{Result, Solution, char_grid};
+use std::collections::HashSet;
+
+
+fn supports_tls(s: &[char]) -> bool {
+  if s.len() < 4 {
+    return false;
+  }
+  let mut has_tls = false;
+  let mut in_hynseq = false;
+  for chx in s.windows(4) {
+    if chx[0] == '[' || chx[0] == ']' {
+      if chx[0] == '[' {
+        in_hynseq = true;
+      } else if chx[0] == ']' {
+        in_hynseq = false;
+      }
+    } else if chx[0] == chx[3] && chx[1] == chx[2] && chx[0] != chx[1] {
+      if in_hynseq {
+        return false;
+      }
+      has_tls = true;
+    }
+  }
+  has_tls
+}
+
+fn supports_ssl(s: &[char]) -> bool {
+  if s.len() < 8 {
+    return false;
+  }
+  let mut aba_seq = HashSet::new();
+  let mut bab_seq = HashSet::new();
+  let mut in_hynseq = false;
+  for chx in s.windows(3) {
+    if chx[0] == '[' || chx[0] == ']' {
+      if chx[0] == '[' {
+        in_hynseq = true;
+      } else if chx[0] == ']' {
+        in_hynseq = false;
+      }
+    } else if chx[0] == chx[2] && chx[0] != chx[1] {
+      if in_hynseq {
+        bab_seq.insert(format!("{1}{0}{1}", chx[0], chx[1]));
+      } else {
+        aba_seq.insert(format!("{0}{1}{0}", chx[0], chx[1]));
+      }
+    }
+  }
+  aba_seq.intersection(&bab_seq).count() > 0
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+  type Input = Vec<Vec<char>>;
+
+  fn parse(data: &str) -> Result<Vec<Vec<char>>> {
+    Ok(char_grid(data))
+  }
+
+  fn part1(addrs: &Vec<Vec<char>>) -> Result<String> {
+    let count = addrs.iter().filter(|s| supports_tls(s)).count();
+    Ok(format!("{}", count))
+  }
+
+  fn part2(addrs: &Vec<Vec<char>>) -> Result<String> {
+    let count = addrs.iter().filter(|s| supports_ssl(s)).count();
+    Ok(format!("{}", count))
+  }
+}
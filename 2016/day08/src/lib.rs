@@ -1,22 +1,10 @@
 // vim: et:sts=2:ts=2:sw=2
 
-extern crate bit_vec;
-#[macro_use] extern crate failure;
-#[macro_use] extern crate lazy_static;
-extern crate regex;
-
+use aoc_common::{Result, Solution, invalid_input, parse_lines, to_usize};
+use lazy_static::lazy_static;
 use regex::{Captures, Regex};
-use std::env;
-use std::fs::File;
 use std::fmt;
-use std::i32;
-use std::io;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::result;
-use std::usize;
 
-type Result<T> = result::Result<T, failure::Error>;
 
 lazy_static! {
   static ref RECT_RE: Regex = Regex::new(r"rect (\d+)x(\d+)").unwrap();
@@ -24,14 +12,7 @@ lazy_static! {
   static ref ROTATE_COL_RE: Regex = Regex::new(r"rotate column x=(\d+) by (\d+)").unwrap();
 }
 
-fn to_usize(s: &str) -> Result<usize> {
-  match s.parse::<usize>() {
-    Ok(n) => Ok(n),
-    Err(_) => Err(format_err!("Unable to parse usize from '{}'", s)),
-  }
-}
-
-trait Op {
+pub trait Op {
   fn run(&self, grid: &mut Grid) -> Result<()>;
 }
 
@@ -48,11 +29,11 @@ impl Rect {
 
   fn from<'t>(item: Option<Captures<'t>>) -> Result<Self> {
     if item.is_none() {
-      return Err(format_err!("No captures found"));
+      return Err(invalid_input!("No captures found"));
     }
     let caps = item.unwrap();
-    let w = to_usize(caps.get(1).ok_or(format_err!("Missing W for Rect"))?.as_str())?;
-    let h = to_usize(caps.get(2).ok_or(format_err!("Missing H for Rect"))?.as_str())?;
+    let w = to_usize(caps.get(1).ok_or(invalid_input!("Missing W for Rect"))?.as_str())?;
+    let h = to_usize(caps.get(2).ok_or(invalid_input!("Missing H for Rect"))?.as_str())?;
     Ok(Self::new(w, h))
   }
 }
@@ -80,11 +61,11 @@ impl RotateRow {
 
   fn from<'t>(item: Option<Captures<'t>>) -> Result<Self> {
     if item.is_none() {
-      return Err(format_err!("No captures found"));
+      return Err(invalid_input!("No captures found"));
     }
     let caps = item.unwrap();
-    let y = to_usize(caps.get(1).ok_or(format_err!("Missing Y for RotateRow"))?.as_str())?;
-    let n = to_usize(caps.get(2).ok_or(format_err!("Missing N for RotateRow"))?.as_str())?;
+    let y = to_usize(caps.get(1).ok_or(invalid_input!("Missing Y for RotateRow"))?.as_str())?;
+    let n = to_usize(caps.get(2).ok_or(invalid_input!("Missing N for RotateRow"))?.as_str())?;
     Ok(Self::new(y, n))
   }
 }
@@ -109,11 +90,11 @@ impl RotateCol {
 
   fn from<'t>(item: Option<Captures<'t>>) -> Result<Self> {
     if item.is_none() {
-      return Err(format_err!("No captures found"));
+      return Err(invalid_input!("No captures found"));
     }
     let caps = item.unwrap();
-    let x = to_usize(caps.get(1).ok_or(format_err!("Missing X for RotateCol"))?.as_str())?;
-    let n = to_usize(caps.get(2).ok_or(format_err!("Missing N for RotateCol"))?.as_str())?;
+    let x = to_usize(caps.get(1).ok_or(invalid_input!("Missing X for RotateCol"))?.as_str())?;
+    let n = to_usize(caps.get(2).ok_or(invalid_input!("Missing N for RotateCol"))?.as_str())?;
     Ok(Self::new(x, n))
   }
 }
@@ -126,7 +107,7 @@ impl Op for RotateCol {
 }
 
 #[derive(Debug)]
-struct Grid {
+pub struct Grid {
   grid: Vec<bit_vec::BitVec>,
   tmp_row: bit_vec::BitVec,
   tmp_col: bit_vec::BitVec,
@@ -138,7 +119,7 @@ impl Grid {
   fn new(x: usize, y: usize) -> Result<Self> {
     const MAX_VAL: usize = (i32::MAX / 1024) as usize;
     if x > MAX_VAL || y > MAX_VAL {
-      Err(format_err!("Dimension too large, x:{} y:{}", x, y))
+      Err(invalid_input!("Dimension too large, x:{} y:{}", x, y))
     } else {
       let mut grid = Vec::new();
       for _ in 0..y {
@@ -153,10 +134,10 @@ impl Grid {
   fn fill_box(&mut self, top_left_x: usize, top_left_y: usize,
               low_right_x: usize, low_right_y: usize) -> Result<()> {
     if top_left_x >= self.x || low_right_x >= self.x || top_left_x > low_right_x {
-      return Err(format_err!("fill_box: X out of bounds: {} {}", top_left_x, low_right_x));
+      return Err(invalid_input!("fill_box: X out of bounds: {} {}", top_left_x, low_right_x));
     }
     if top_left_y >= self.y || low_right_y >= self.y || top_left_y > low_right_y {
-      return Err(format_err!("fill_box: Y out of bounds: {} {}", top_left_y, low_right_y));
+      return Err(invalid_input!("fill_box: Y out of bounds: {} {}", top_left_y, low_right_y));
     }
     for i in top_left_y..=low_right_y {
       for j in top_left_x..=low_right_x {
@@ -168,17 +149,16 @@ impl Grid {
 
   fn rotate_row(&mut self, y: usize, n: usize) -> Result<()> {
     if y >= self.y {
-      return Err(format_err!("rotate_row: Y out of bounds: {}", y));
+      return Err(invalid_input!("rotate_row: Y out of bounds: {}", y));
     }
     let n = n % self.x;
     if n == 0 {
       return Ok(());
     }
     let new_row = self.grid.get_mut(y)
-                      .ok_or(format_err!("Row out of bounds: {}", y))?;
+                      .ok_or(invalid_input!("Row out of bounds: {}", y))?;
     let old_row = &mut self.tmp_row;
-    old_row.clear();
-    old_row.union(new_row);
+    old_row.clone_from(new_row);
     for i in 0..self.x {
       let idx = (i + n) % self.x;
       new_row.set(idx, old_row.get(i).unwrap());
@@ -188,19 +168,19 @@ impl Grid {
 
   fn rotate_col(&mut self, x: usize, n: usize) -> Result<()> {
     if x >= self.x {
-      return Err(format_err!("rotate_col: X out of bounds: {}", x));
+      return Err(invalid_input!("rotate_col: X out of bounds: {}", x));
     }
     let n = n % self.y;
     if n == 0 {
       return Ok(());
     }
     let old_col = &mut self.tmp_col;
-    old_col.clear();
+    old_col.fill(false);
     for i in 0..self.y {
       let row = self.grid.get_mut(i)
-                         .ok_or(format_err!("Row out of bounds: {}", i))?;
+                         .ok_or(invalid_input!("Row out of bounds: {}", i))?;
       let val = row.get(x)
-                   .ok_or(format_err!("Col out of bounds: {}", x))?;
+                   .ok_or(invalid_input!("Col out of bounds: {}", x))?;
       if val {
         old_col.set(i, true);
       }
@@ -229,49 +209,53 @@ impl fmt::Display for Grid {
   }
 }
 
-fn read_input<P>(path: P) -> Result<Vec<String>>
-    where P: AsRef<Path> + fmt::Debug {
-  let f = File::open(&path)?;
-  let reader = io::BufReader::new(f);
-  Ok(reader.lines().collect::<result::Result<Vec<_>, _>>()?)
-}
-
-fn to_op(s: &str) -> Result<Box<Op>> {
-  if RECT_RE.is_match(&s) {
-    let r = Rect::from(RECT_RE.captures(&s))?;
+fn to_op(s: &str) -> Result<Box<dyn Op>> {
+  if RECT_RE.is_match(s) {
+    let r = Rect::from(RECT_RE.captures(s))?;
     //DBG println!("{:?}", r);
     Ok(Box::new(r))
-  } else if ROTATE_ROW_RE.is_match(&s) {
-    let r = RotateRow::from(ROTATE_ROW_RE.captures(&s))?;
+  } else if ROTATE_ROW_RE.is_match(s) {
+    let r = RotateRow::from(ROTATE_ROW_RE.captures(s))?;
     //DBG println!("{:?}", r);
     Ok(Box::new(r))
-  } else if ROTATE_COL_RE.is_match(&s) {
-    let r = RotateCol::from(ROTATE_COL_RE.captures(&s))?;
+  } else if ROTATE_COL_RE.is_match(s) {
+    let r = RotateCol::from(ROTATE_COL_RE.captures(s))?;
     //DBG println!("{:?}", r);
     Ok(Box::new(r))
   } else {
-    Err(format_err!("Bad Op format: {}", s))
+    Err(invalid_input!("Bad Op format: {}", s))
   }
 }
 
-fn solve<P>(path: P) -> Result<usize>
-    where P: AsRef<Path> + fmt::Debug {
+fn run_ops(ops: &[Box<dyn Op>]) -> Result<Grid> {
   let mut grid = Grid::new(50, 6)?;
   //DBG println!("{:?}", grid);
-  for line in read_input(path)? {
-    let op = to_op(&line)?;
+  for op in ops {
     op.run(&mut grid)?;
     //DBG println!("{:?}", grid);
   }
-  println!("\n{}", grid);
-  Ok(grid.true_pixel_count())
+  Ok(grid)
 }
 
-fn main() -> Result<()> {
-  let args = env::args().collect::<Vec<_>>();
-  let path = PathBuf::from(args.get(1).map(|s| s.as_str()).unwrap_or("input.txt"));
-  println!("Pixel count: {}", solve(path)?);
-  Ok(())
+pub struct Day08;
+
+impl Solution for Day08 {
+  type Input = Vec<Box<dyn Op>>;
+
+  fn parse(data: &str) -> Result<Vec<Box<dyn Op>>> {
+    parse_lines(data, to_op)
+  }
+
+  fn part1(ops: &Vec<Box<dyn Op>>) -> Result<String> {
+    let grid = run_ops(ops)?;
+    Ok(format!("{}", grid.true_pixel_count()))
+  }
+
+  // The answer is whatever letters the lit pixels spell out.
+  fn part2(ops: &Vec<Box<dyn Op>>) -> Result<String> {
+    let grid = run_ops(ops)?;
+    Ok(grid.to_string().trim_end().to_owned())
+  }
 }
 
 #[cfg(test)]
@@ -0,0 +1,37 @@
+use aoc_common::{Result, Solution, digits};
+
+
+pub struct Day01;
+
+impl Solution for Day01 {
+  type Input = Vec<u32>;
+
+  fn parse(data: &str) -> Result<Vec<u32>> {
+    digits(data, 10)
+  }
+
+  fn part1(xs: &Vec<u32>) -> Result<String> {
+    let mut t = 0;
+    let mut m = xs[xs.len() - 1];
+    for &n in xs {
+      if m == n {
+        t += m;
+      }
+      m = n;
+    }
+    Ok(format!("{}", t))
+  }
+
+  fn part2(xs: &Vec<u32>) -> Result<String> {
+    let steps = xs.len() / 2;
+    let mut t = 0;
+    for (i, n) in xs.iter().enumerate() {
+      let j = (i + steps) % xs.len();
+      let m = xs[j];
+      if m == *n {
+        t += m;
+      }
+    }
+    Ok(format!("{}", t))
+  }
+}
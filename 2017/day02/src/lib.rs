@@ -0,0 +1,41 @@
+use aoc_common::{Result, Solution, whitespace_separated};
+use std::cmp;
+
+
+pub struct Day02;
+
+impl Solution for Day02 {
+  type Input = Vec<Vec<i64>>;
+
+  fn parse(data: &str) -> Result<Vec<Vec<i64>>> {
+    whitespace_separated(data)
+  }
+
+  fn part1(rows: &Vec<Vec<i64>>) -> Result<String> {
+    let mut checksum = 0;
+    for row in rows {
+      let min = row.iter().fold(i64::MAX, |acc, &n| cmp::min(acc, n));
+      let max = row.iter().fold(i64::MIN, |acc, &n| cmp::max(acc, n));
+      checksum += max - min;
+    }
+    Ok(format!("{}", checksum))
+  }
+
+  fn part2(rows: &Vec<Vec<i64>>) -> Result<String> {
+    let mut checksum = 0;
+    for row in rows {
+      for (i, n) in row.iter().enumerate() {
+        for &m in &row[(i + 1)..] {
+          if n % m == 0 {
+            checksum += n / m;
+            break;
+          } else if m % n == 0 {
+            checksum += m / n;
+            break;
+          }
+        }
+      }
+    }
+    Ok(format!("{}", checksum))
+  }
+}
@@ -1,32 +1,14 @@
-#[macro_use] extern crate failure;
+use aoc_common::{Result, Solution, invalid_input, to_i64};
 
-use std::fs::File;
-use std::i64;
-use std::io::Read;
-use std::path::{Path, PathBuf};
-use std::result;
 
-type Result<T> = result::Result<T, failure::Error>;
-
-fn to_i64(s: &str) -> Result<i64> {
-  match s.parse::<i64>() {
-    Ok(n) => Ok(n),
-    Err(_) => Err(format_err!("Unable to parse i64 from: {}", s)),
-  }
-}
-
-fn read_data_file<P>(filename: P) -> Result<i64>
-    where P: AsRef<Path> {
-  let mut f = File::open(&filename)?;
-  let mut data = String::new();
-  f.read_to_string(&mut data)?;
+fn parse_square(data: &str) -> Result<i64> {
   let row = data.split_whitespace()
                 .map(to_i64)
                 .collect::<Result<Vec<_>>>()?;
   if row.is_empty() {
-    Err(format_err!("Insufficient input data"))
+    Err(invalid_input!("Insufficient input data"))
   } else if row[0] < 1 {
-    Err(format_err!("Value too small: {}", row[0]))
+    Err(invalid_input!("Value too small: {}", row[0]))
   } else {
     Ok(row[0])
   }
@@ -46,8 +28,8 @@ enum Direction {
 }
 
 impl Direction {
-  fn to_vec(&self) -> Vector {
-    match *self {
+  fn to_vec(self) -> Vector {
+    match self {
       Direction::East => Vector{dx: 1, dy: 0},
       Direction::North => Vector{dx: 0, dy: -1},
       Direction::West => Vector{dx: -1, dy: 0},
@@ -80,8 +62,8 @@ impl SpiralWalker {
     SpiralWalker {
       x: center_x,
       y: center_y,
-      center_x: center_x,
-      center_y: center_y,
+      center_x,
+      center_y,
       side_size: 1,
       dir: Direction::East,
     }
@@ -116,7 +98,7 @@ fn idx(x: i64, y: i64, row_size: i64) -> usize {
   (x + y * row_size) as usize
 }
 
-fn sum_box(arr: &Vec<i64>, x: i64, y: i64, row_size: i64) -> i64 {
+fn sum_box(arr: &[i64], x: i64, y: i64, row_size: i64) -> i64 {
   let mut sum = 0;
   for i in -1..2 {
     for j in -1..2 {
@@ -129,13 +111,19 @@ fn sum_box(arr: &Vec<i64>, x: i64, y: i64, row_size: i64) -> i64 {
   sum
 }
 
-fn solve<P>(input: P) -> Result<String>
-    where P: AsRef<Path> {
-  let n = read_data_file(input)?;
-  if n == 1 {
-    return Ok("0".to_string());
+pub struct Day03;
+
+impl Solution for Day03 {
+  type Input = i64;
+
+  fn parse(data: &str) -> Result<i64> {
+    parse_square(data)
   }
-  let side = {
+
+  fn part1(&n: &i64) -> Result<String> {
+    if n == 1 {
+      return Ok("0".to_string());
+    }
     let mut lvl = 0;
     let mut min: i64;
     let mut max = 1;
@@ -144,36 +132,52 @@ fn solve<P>(input: P) -> Result<String>
       lvl += 1;
       side = lvl * 2;
       min = max + 1;
-      max = max + side * 4;
+      max += side * 4;
       if min <= n && n <= max {
         break;
       }
     }
-    if side % 2 != 0 {
-      return Err(format_err!("Box size not odd: {}", side + 1));
-    }
-    side as i64 + 3
-  };
-  let mid = side / 2 + 1;
-  let arr_area = side * side;
-  let mut arr = Vec::with_capacity(arr_area as usize);
-  for _ in 0..arr_area {
-    arr.push(0);
+    let norm = (n - min) % side;
+    let mid = side / 2 - 1;
+    let steps = (mid - norm).abs() + lvl;
+    Ok(format!("{}", steps))
   }
-  let mut w = SpiralWalker::new(mid, mid);
-  arr[idx(w.x, w.y, side)] = 1;
-  let mut sum: i64 = 0;
-  while sum < n {
-    w.advance();
-    sum = sum_box(&arr, w.x, w.y, side);
-    arr[idx(w.x, w.y, side)] = sum;
-  }
-  Ok(format!("{}", sum))
-}
 
-fn main() {
-  match solve(PathBuf::from("input.txt")) {
-    Ok(msg) => println!("Result: {}", msg),
-    Err(err) => println!("Error: {}", err),
+  fn part2(&n: &i64) -> Result<String> {
+    if n == 1 {
+      return Ok("0".to_string());
+    }
+    let side = {
+      let mut lvl = 0;
+      let mut min: i64;
+      let mut max = 1;
+      let mut side: i64;
+      loop {
+        lvl += 1;
+        side = lvl * 2;
+        min = max + 1;
+        max += side * 4;
+        if min <= n && n <= max {
+          break;
+        }
+      }
+      if side % 2 != 0 {
+        return Err(invalid_input!("Box size not odd: {}", side + 1));
+      }
+      side + 3
+    };
+    let mid = side / 2 + 1;
+    let arr_area = side * side;
+    let mut arr = vec![0; arr_area as usize];
+    let mut w = SpiralWalker::new(mid, mid);
+    arr[idx(w.x, w.y, side)] = 1;
+    let mut sum: i64 = 0;
+    // The answer is the first value written that is larger than the input.
+    while sum <= n {
+      w.advance();
+      sum = sum_box(&arr, w.x, w.y, side);
+      arr[idx(w.x, w.y, side)] = sum;
+    }
+    Ok(format!("{}", sum))
   }
 }
@@ -0,0 +1,43 @@
+use aoc_common::{Result, Solution};
+use std::collections::HashMap;
+
+
+fn has_duplicates<I>(words: I) -> bool
+    where I: Iterator<Item = String> {
+  let counts = words.fold(HashMap::new(), |mut acc, s| {
+                      *acc.entry(s).or_insert(0) += 1; acc
+                    });
+  counts.values().any(|n| *n > 1)
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+  type Input = Vec<Vec<String>>;
+
+  fn parse(data: &str) -> Result<Vec<Vec<String>>> {
+    Ok(data.lines()
+           .map(|line| line.split_whitespace().map(ToOwned::to_owned).collect())
+           .collect())
+  }
+
+  fn part1(lines: &Vec<Vec<String>>) -> Result<String> {
+    let total = lines.iter()
+                     .filter(|words| !has_duplicates(words.iter().cloned()))
+                     .count();
+    Ok(format!("{}", total))
+  }
+
+  fn part2(lines: &Vec<Vec<String>>) -> Result<String> {
+    let total = lines.iter()
+                     .filter(|words| {
+                       !has_duplicates(words.iter().map(|s| {
+                         let mut ss: Vec<_> = s.chars().collect();
+                         ss.sort();
+                         ss.into_iter().collect::<String>()
+                       }))
+                     })
+                     .count();
+    Ok(format!("{}", total))
+  }
+}
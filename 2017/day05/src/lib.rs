@@ -0,0 +1,39 @@
+use aoc_common::{Result, Solution, whitespace_separated};
+
+
+fn count_steps<F>(jumps: &[i64], adjust: F) -> usize
+    where F: Fn(i64) -> i64 {
+  let mut jumps = jumps.to_vec();
+  let mut idx: i64 = 0;
+  let mut steps = 0;
+  loop {
+    steps += 1;
+    let i = idx as usize;
+    idx += jumps[i];
+    jumps[i] += adjust(jumps[i]);
+    if idx < 0 || idx >= jumps.len() as i64 {
+      break;
+    }
+  }
+  steps
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+  type Input = Vec<i64>;
+
+  fn parse(data: &str) -> Result<Vec<i64>> {
+    let rows = whitespace_separated(data)?;
+    Ok(rows.into_iter().flatten().collect())
+  }
+
+  fn part1(jumps: &Vec<i64>) -> Result<String> {
+    Ok(format!("{}", count_steps(jumps, |_| 1)))
+  }
+
+  fn part2(jumps: &Vec<i64>) -> Result<String> {
+    let steps = count_steps(jumps, |n| if n >= 3 { -1 } else { 1 });
+    Ok(format!("{}", steps))
+  }
+}
@@ -0,0 +1,76 @@
+use aoc_common::{Result, Solution, whitespace_separated};
+use std::collections::HashMap;
+
+
+fn format_banks(banks: &[i64]) -> Result<String> {
+  use std::io::Cursor;
+  use std::io::Write;
+  let mut buf = Cursor::new(Vec::<u8>::new());
+  let mut sep = "";
+  for n in banks {
+    write!(&mut buf, "{}{}", sep, n)?;
+    sep = ",";
+  }
+  Ok(String::from_utf8_lossy(buf.get_ref()).into_owned())
+}
+
+// Returns index in banks of the largest bank.
+fn find_largest(banks: &[i64]) -> usize {
+  let mut res_idx = 0;
+  for (idx, n) in banks.iter().enumerate() {
+    if *n > banks[res_idx] {
+      res_idx = idx;
+    }
+  }
+  res_idx
+}
+
+// Returns the number of redistributions until a configuration repeats, and
+// the length of the loop that repeat closes.
+fn find_cycle(banks: &[i64]) -> Result<(usize, usize)> {
+  let mut banks = banks.to_vec();
+  let mut seen = HashMap::new();
+  let mut count = 0;
+  seen.insert(format_banks(&banks)?, count);
+  loop {
+    let idx = find_largest(&banks);
+    let mut n = banks[idx];
+    banks[idx] = 0;
+    let mut i = idx + 1;
+    while n > 0 {
+      if i >= banks.len() {
+        i = 0;
+      }
+      banks[i] += 1;
+      n -= 1;
+      i += 1;
+    }
+    count += 1;
+    let latest = format_banks(&banks)?;
+    if let Some(m) = seen.get(&latest) {
+      return Ok((count, count - m));
+    }
+    seen.insert(latest, count);
+  }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+  type Input = Vec<i64>;
+
+  fn parse(data: &str) -> Result<Vec<i64>> {
+    let rows = whitespace_separated(data)?;
+    Ok(rows.into_iter().flatten().collect())
+  }
+
+  fn part1(banks: &Vec<i64>) -> Result<String> {
+    let (steps, _) = find_cycle(banks)?;
+    Ok(format!("{}", steps))
+  }
+
+  fn part2(banks: &Vec<i64>) -> Result<String> {
+    let (_, loop_len) = find_cycle(banks)?;
+    Ok(format!("{}", loop_len))
+  }
+}
@@ -0,0 +1,141 @@
+use aoc_common::{Result, Solution, invalid_input, no_solution, to_i64};
+use std::collections::HashMap;
+
+
+#[derive(Debug)]
+pub struct Tower {
+  weight: i64,
+  children: Vec<String>,
+  parent: Option<String>,
+}
+
+pub type TowerMap = HashMap<String, Tower>;
+
+impl Tower {
+  fn new() -> Tower {
+    Tower {
+      weight: 0,
+      children: Vec::new(),
+      parent: None,
+    }
+  }
+
+  fn set_weight(&mut self, weight: i64) {
+    self.weight = weight;
+  }
+
+  fn set_children(&mut self, children: Vec<String>) {
+    self.children = children;
+  }
+
+  fn set_parent(&mut self, parent: &str) {
+    self.parent = Some(parent.to_string());
+  }
+}
+
+fn insert_tower(towers: &mut TowerMap, name: &str) {
+  if !towers.contains_key(name) {
+    towers.insert(name.to_string(), Tower::new());
+  }
+}
+
+fn get_tower<'a>(towers: &'a TowerMap, name: &str) -> Result<&'a Tower> {
+  towers.get(name).ok_or_else(|| invalid_input!("Unknown tower: {}", name))
+}
+
+fn find_root_tower(towers: &TowerMap) -> Result<String> {
+  let mut root_name = towers.keys()
+                            .next()
+                            .ok_or(invalid_input!("No towers defined"))?
+                            .clone();
+  loop {
+    if let Some(t) = towers.get(&root_name) {
+      if let Some(ref p) = t.parent {
+        root_name = p.clone();
+      } else {
+        return Ok(root_name);
+      }
+    }
+  }
+}
+
+// Weight of a tower plus everything stacked on top of it.
+fn total_weight(towers: &TowerMap, name: &str) -> Result<i64> {
+  let tower = get_tower(towers, name)?;
+  let mut total = tower.weight;
+  for child in tower.children.iter() {
+    total += total_weight(towers, child)?;
+  }
+  Ok(total)
+}
+
+// Walks up the unbalanced branch. The deepest tower whose own children are
+// balanced is the one with the wrong weight.
+fn find_correction(towers: &TowerMap, name: &str) -> Result<Option<i64>> {
+  let tower = get_tower(towers, name)?;
+  let totals = tower.children.iter()
+                    .map(|child| total_weight(towers, child))
+                    .collect::<Result<Vec<_>>>()?;
+  let odd_idx = totals.iter()
+                      .position(|t| totals.iter().filter(|u| *u == t).count() == 1);
+  let odd_idx = match odd_idx {
+    Some(idx) if totals.len() > 2 => idx,
+    _ => return Ok(None),
+  };
+  let odd_name = &tower.children[odd_idx];
+  if let Some(weight) = find_correction(towers, odd_name)? {
+    return Ok(Some(weight));
+  }
+  let expected = totals[(odd_idx + 1) % totals.len()];
+  let odd = get_tower(towers, odd_name)?;
+  Ok(Some(odd.weight + expected - totals[odd_idx]))
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+  type Input = TowerMap;
+
+  fn parse(data: &str) -> Result<TowerMap> {
+    let mut towers = TowerMap::new();
+    for line in data.lines() {
+      // Convert: nafjxju (347) -> hptnh, zcyjg
+      // To: ['nafjxju', '347', 'hptnh', 'zcyjg']
+      let mut row = line.chars()
+                        .filter(|c| c.is_ascii() && (c.is_whitespace() || c.is_alphanumeric()))
+                        .collect::<String>()
+                        .split_whitespace()
+                        .map(ToOwned::to_owned)
+                        .collect::<Vec<String>>();
+      if row.len() < 2 {
+        return Err(invalid_input!("Missing tower name or weight"));
+      }
+      let children = row.split_off(2);
+      let weight = to_i64(&row.pop().unwrap())?;
+      let name = row.pop().unwrap();
+      for ch_name in children.iter() {
+        insert_tower(&mut towers, ch_name);
+        if let Some(child) = towers.get_mut(ch_name.as_str()) {
+          child.set_parent(name.as_str());
+        }
+      }
+      insert_tower(&mut towers, name.as_str());
+      if let Some(tower) = towers.get_mut(&name) {
+        tower.set_weight(weight);
+        tower.set_children(children);
+      }
+    }
+    Ok(towers)
+  }
+
+  fn part1(towers: &TowerMap) -> Result<String> {
+    find_root_tower(towers)
+  }
+
+  fn part2(towers: &TowerMap) -> Result<String> {
+    let root_name = find_root_tower(towers)?;
+    let weight = find_correction(towers, &root_name)?
+        .ok_or_else(|| no_solution!("The tower is already balanced"))?;
+    Ok(format!("{}", weight))
+  }
+}
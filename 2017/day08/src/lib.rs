@@ -0,0 +1,111 @@
+use aoc_common::{Result, Solution, invalid_input, parse_lines, to_i64};
+use std::collections::HashMap;
+
+
+fn get_op(name: &str) -> Result<fn(i64, i64) -> i64> {
+  match name {
+    "inc" => Ok((|x, n| x + n) as fn(i64, i64) -> i64),
+    "dec" => Ok((|x, n| x - n) as fn(i64, i64) -> i64),
+    _ => Err(invalid_input!("Invalid op: {}", name)),
+  }
+}
+
+fn get_cmp(name: &str) -> Result<fn(i64, i64) -> bool> {
+  match name {
+    ">" => Ok((|m, n| m > n) as fn(i64, i64) -> bool),
+    ">=" => Ok((|m, n| m >= n) as fn(i64, i64) -> bool),
+    "<" => Ok((|m, n| m < n) as fn(i64, i64) -> bool),
+    "<=" => Ok((|m, n| m <= n) as fn(i64, i64) -> bool),
+    "==" => Ok((|m, n| m == n) as fn(i64, i64) -> bool),
+    "!=" => Ok((|m, n| m != n) as fn(i64, i64) -> bool),
+    _ => Err(invalid_input!("Invalid cmp: {}", name)),
+  }
+}
+
+pub struct Instruction {
+  reg: String,
+  op_fn: fn(i64, i64) -> i64,
+  adj: i64,
+  cmp_reg: String,
+  cmp_fn: fn(i64, i64) -> bool,
+  cmp_val: i64,
+}
+
+impl Instruction {
+  fn parse(line: &str) -> Result<Instruction> {
+    // 0   1         2     3  4   5                 6
+    // \w+ (inc|dec) -?\d+ if \w+ (<|>|==|!=|>=|<=) -?\d+
+    let row = line.split_whitespace()
+                  .collect::<Vec<_>>();
+    if row.len() != 7 {
+      return Err(invalid_input!("Missing instruction parts, have {}", row.len()));
+    }
+    if row[3] != "if" {
+      return Err(invalid_input!("Invalid instruction, no if"));
+    }
+    Ok(Instruction {
+      reg: row[0].to_string(),
+      op_fn: get_op(row[1])?,
+      adj: to_i64(row[2])?,
+      cmp_reg: row[4].to_string(),
+      cmp_fn: get_cmp(row[5])?,
+      cmp_val: to_i64(row[6])?,
+    })
+  }
+}
+
+fn get_reg_val(regs: &HashMap<String, i64>, name: &str) -> i64 {
+  if let Some(n) = regs.get(name) {
+    *n
+  } else {
+    0
+  }
+}
+
+fn instr_passes(regs: &mut HashMap<String, i64>, instr: &Instruction) -> bool {
+  let cmp_reg_val = get_reg_val(regs, &instr.cmp_reg);
+  (instr.cmp_fn)(cmp_reg_val, instr.cmp_val)
+}
+
+fn update_reg(regs: &mut HashMap<String, i64>, instr: &Instruction) {
+  let reg_val = get_reg_val(regs, &instr.reg);
+  let result = (instr.op_fn)(reg_val, instr.adj);
+  regs.insert(instr.reg.to_string(), result);
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+  type Input = Vec<Instruction>;
+
+  fn parse(data: &str) -> Result<Vec<Instruction>> {
+    parse_lines(data, Instruction::parse)
+  }
+
+  fn part1(instrs: &Vec<Instruction>) -> Result<String> {
+    let mut regs: HashMap<String, i64> = HashMap::new();
+    for instr in instrs {
+      if instr_passes(&mut regs, instr) {
+        update_reg(&mut regs, instr);
+      }
+    }
+    let mut max = i64::MIN;
+    for n in regs.values() {
+      max = i64::max(max, *n);
+    }
+    Ok(format!("{}", max))
+  }
+
+  fn part2(instrs: &Vec<Instruction>) -> Result<String> {
+    let mut regs: HashMap<String, i64> = HashMap::new();
+    let mut max = i64::MIN;
+    for instr in instrs {
+      if instr_passes(&mut regs, instr) {
+        update_reg(&mut regs, instr);
+        let reg_val = get_reg_val(&regs, &instr.reg);
+        max = i64::max(max, reg_val);
+      }
+    }
+    Ok(format!("{}", max))
+  }
+}
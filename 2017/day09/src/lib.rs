@@ -0,0 +1,70 @@
+use aoc_common::{Result, Solution};
+
+
+pub struct Day09;
+
+impl Solution for Day09 {
+  type Input = String;
+
+  fn parse(data: &str) -> Result<String> {
+    Ok(data.to_owned())
+  }
+
+  fn part1(data: &String) -> Result<String> {
+    let mut in_garbage = false;
+    let mut skip_next = false;
+    let mut score = 0;
+    let mut nesting = 0;
+    for ch in data.chars() {
+      if skip_next {
+        skip_next = false;
+        continue;
+      }
+      if in_garbage {
+        match ch {
+          '!' => skip_next = true,
+          '>' => in_garbage = false,
+          _ => {},
+        }
+      } else {
+        match ch {
+          '{' => {
+            nesting += 1;
+            score += nesting;
+          },
+          '}' => nesting -= 1,
+          '<' => in_garbage = true,
+          _ => {},
+        }
+      }
+    }
+    Ok(format!("{}", score))
+  }
+
+  fn part2(data: &String) -> Result<String> {
+    let mut in_garbage = false;
+    let mut skip_next = false;
+    let mut score = 0;
+    for ch in data.chars() {
+      if skip_next {
+        skip_next = false;
+        continue;
+      }
+      if in_garbage {
+        match ch {
+          '!' => skip_next = true,
+          '>' => in_garbage = false,
+          _ => score += 1,
+        }
+      } else {
+        match ch {
+          '{' => {},
+          '}' => {},
+          '<' => in_garbage = true,
+          _ => {},
+        }
+      }
+    }
+    Ok(format!("{}", score))
+  }
+}
@@ -1,45 +1,15 @@
-#[macro_use] extern crate failure;
-
+use aoc_common::{Result, Solution, comma_separated};
 use std::cell::RefCell;
 use std::fmt;
 use std::fmt::{Display, Write};
-use std::fs::File;
-use std::io::Read;
-use std::path::{Path, PathBuf};
 use std::ops::Deref;
 use std::rc::Rc;
-use std::result;
 
-type Result<T> = result::Result<T, failure::Error>;
 
 const LIST_SIZE: usize = 256;
 
-fn read_data_file<P>(filename: P) -> Result<Vec<u8>>
-    where P: AsRef<Path> {
-  let mut f = File::open(&filename)?;
-  let mut data = String::new();
-  f.read_to_string(&mut data)?;
-  let line = data.lines()
-                 .next()
-                 .ok_or(format_err!("No input line"))?;
-  let mut res = line.as_bytes()
-                    .iter()
-                    .map(ToOwned::to_owned)
-                    .collect::<Vec<u8>>();
-  res.push(17);
-  res.push(31);
-  res.push(73);
-  res.push(47);
-  res.push(23);
-  Ok(res)
-}
-
-fn xor_combine(xs: &Vec<u8>, start: usize, len: usize) -> u8 {
-  let mut n = xs[start];
-  for i in (start+1)..(start+len) {
-    n = n ^ xs[i];
-  }
-  n
+fn xor_combine(xs: &[u8], start: usize, len: usize) -> u8 {
+  xs[start..(start+len)].iter().fold(0, |acc, b| acc ^ b)
 }
 
 struct Circular {
@@ -69,6 +39,11 @@ impl Circular {
     }
   }
 
+  fn get(&self, idx: usize) -> usize {
+    let xs = self.xs_ref.borrow();
+    xs[idx] as usize
+  }
+
   fn densify(&mut self) {
     let mut res = Vec::new();
     {
@@ -103,26 +78,44 @@ fn advance(cur: usize, len: usize) -> usize {
   (cur + (len % LIST_SIZE)) % LIST_SIZE
 }
 
-fn solve<P>(input: P) -> Result<String>
-    where P: AsRef<Path> {
-  let data = read_data_file(input)?;
-  let mut rope = Circular::new();
-  let mut idx = 0;
-  let mut skip = 0;
-  for _ in 0..64 {
-    for n in data.iter() {
-      rope.reverse_range(idx, *n as usize);
-      idx = advance(idx, *n as usize + skip);
-      skip += 1;
+pub struct Day10;
+
+impl Solution for Day10 {
+  type Input = String;
+
+  fn parse(data: &str) -> Result<String> {
+    // An empty input is still a valid key for the knot hash.
+    let line = data.lines().next().unwrap_or("");
+    Ok(line.trim().to_owned())
+  }
+
+  // The first part reads the line as a list of lengths.
+  fn part1(line: &String) -> Result<String> {
+    let lengths = comma_separated::<usize>(line)?;
+    let rope = Circular::new();
+    let mut idx = 0;
+    for (skip, n) in lengths.into_iter().flatten().enumerate() {
+      rope.reverse_range(idx, n);
+      idx = advance(idx, n + skip);
     }
+    Ok(format!("{}", rope.get(0) * rope.get(1)))
   }
-  rope.densify();
-  Ok(rope.hex_digest()?)
-}
 
-fn main() {
-  match solve(PathBuf::from("input.txt")) {
-    Ok(msg) => println!("Result: {}", msg),
-    Err(err) => println!("Error: {}", err),
+  // The second part reads it as bytes, followed by a fixed suffix.
+  fn part2(line: &String) -> Result<String> {
+    let mut data = line.as_bytes().to_vec();
+    data.extend(vec![17, 31, 73, 47, 23]);
+    let mut rope = Circular::new();
+    let mut idx = 0;
+    let mut skip = 0;
+    for _ in 0..64 {
+      for n in data.iter() {
+        rope.reverse_range(idx, *n as usize);
+        idx = advance(idx, *n as usize + skip);
+        skip += 1;
+      }
+    }
+    rope.densify();
+    rope.hex_digest()
   }
 }
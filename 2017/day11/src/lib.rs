@@ -1,14 +1,8 @@
-#[macro_use] extern crate failure;
-
+use aoc_common::{Result, Solution, invalid_input};
 use std::default::Default;
 use std::fmt;
 use std::fmt::Display;
-use std::fs::File;
-use std::io::Read;
-use std::path::{Path, PathBuf};
-use std::result;
 
-type Result<T> = result::Result<T, failure::Error>;
 
 // https://www.redblobgames.com/grids/hexagons/
 #[derive(Debug, Default)]
@@ -24,8 +18,8 @@ impl HexAxialCoord {
 
   fn from(q: i64, r: i64) -> HexAxialCoord {
     HexAxialCoord {
-      q: q,
-      r: r,
+      q,
+      r,
     }
   }
 
@@ -38,7 +32,7 @@ impl HexAxialCoord {
       "ne" => Ok(HexAxialCoord::from(1, -1)),
       "sw" => Ok(HexAxialCoord::from(-1, 1)),
       "se" => Ok(HexAxialCoord::from(1, 0)),
-      _ => Err(format_err!("Unknown direction: {}", dir)),
+      _ => Err(invalid_input!("Unknown direction: {}", dir)),
     }
   }
 
@@ -62,35 +56,39 @@ impl Display for HexAxialCoord {
   }
 }
 
-fn read_data_file<P>(filename: P) -> Result<Vec<String>>
-    where P: AsRef<Path> {
-  let mut f = File::open(&filename)?;
-  let mut data = String::new();
-  f.read_to_string(&mut data)?;
-  let mut res = Vec::new();
-  for s in data.split_whitespace() {
-    let mut ss = s.split(',')
-                  .map(ToOwned::to_owned)
-                  .collect::<Vec<_>>();
-    res.append(&mut ss);
+pub struct Day11;
+
+impl Solution for Day11 {
+  type Input = Vec<String>;
+
+  fn parse(data: &str) -> Result<Vec<String>> {
+    let mut res = Vec::new();
+    for s in data.split_whitespace() {
+      let mut ss = s.split(',')
+                    .map(ToOwned::to_owned)
+                    .collect::<Vec<_>>();
+      res.append(&mut ss);
+    }
+    Ok(res)
   }
-  Ok(res)
-}
 
-fn solve<P>(input: P) -> Result<String>
-    where P: AsRef<Path> {
-  let directions = read_data_file(input)?;
-  let zero = HexAxialCoord::new();
-  let mut hex = HexAxialCoord::new();
-  for dir in directions {
-    hex.step_dir(&dir)?;
+  fn part1(directions: &Vec<String>) -> Result<String> {
+    let zero = HexAxialCoord::new();
+    let mut hex = HexAxialCoord::new();
+    for dir in directions {
+      hex.step_dir(dir)?;
+    }
+    Ok(format!("{}", hex.distance_from(&zero)))
   }
-  Ok(format!("{}", hex.distance_from(&zero)))
-}
 
-fn main() {
-  match solve(PathBuf::from("input.txt")) {
-    Ok(msg) => println!("Result: {}", msg),
-    Err(err) => println!("Error: {}", err),
+  fn part2(directions: &Vec<String>) -> Result<String> {
+    let zero = HexAxialCoord::new();
+    let mut hex = HexAxialCoord::new();
+    let mut max = 0;
+    for dir in directions {
+      hex.step_dir(dir)?;
+      max = i64::max(max, hex.distance_from(&zero));
+    }
+    Ok(format!("{}", max))
   }
 }
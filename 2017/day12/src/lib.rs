@@ -0,0 +1,94 @@
+use aoc_common::{Result, Solution, to_i64};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+
+#[derive(Debug, Default)]
+pub struct Chan {
+  directs: Vec<i64>,
+}
+
+impl Chan {
+  fn new(directs: &[i64]) -> Chan {
+    Chan {
+      directs: directs.to_vec()
+    }
+  }
+}
+
+type Buckets = HashMap<i64, Rc<RefCell<HashSet<i64>>>>;
+
+fn rc_to_usize<T>(rc: &Rc<T>) -> usize {
+  Rc::as_ptr(rc) as usize
+}
+
+// Maps every program to the shared set of programs in its group.
+fn build_groups(chans: &HashMap<i64, Chan>) -> Buckets {
+  let mut buckets: Buckets = HashMap::new();
+  for n in chans.keys() {
+    let chan = chans.get(n).unwrap();
+    let mut new_set: HashSet<i64> = HashSet::new();
+    new_set.extend(&chan.directs);
+    new_set.insert(*n);
+    for p in &chan.directs {
+      if n == p {
+        continue;
+      }
+      if let Some(p_group_ref) = buckets.get(p) {
+        let p_group = p_group_ref.borrow();
+        new_set.extend(p_group.iter());
+      }
+    }
+    let new_set_ref = Rc::new(RefCell::new(new_set));
+    let new_set = new_set_ref.borrow();
+    for q in new_set.iter() {
+      buckets.insert(*q, Rc::clone(&new_set_ref));
+    }
+  }
+  buckets
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+  type Input = HashMap<i64, Chan>;
+
+  fn parse(data: &str) -> Result<HashMap<i64, Chan>> {
+  let mut res = HashMap::new();
+  for line in data.lines() {
+    let toks =
+        line.split_whitespace()
+            .map(|s| s.chars().filter(|c| c.is_ascii_digit()).collect::<String>())
+            .filter(|s| !s.is_empty())
+            .map(|s| to_i64(&s))
+            .collect::<Result<Vec<i64>>>()?;
+    if let Some((id, directs)) = toks.split_first() {
+      res.insert(*id, Chan::new(directs));
+    }
+  }
+  Ok(res)
+  }
+
+  fn part1(chans: &HashMap<i64, Chan>) -> Result<String> {
+    let buckets = build_groups(chans);
+    let mut count = 0;
+    if let Some(zero_ref) = buckets.get(&0) {
+      let zero = zero_ref.borrow();
+      count = zero.len();
+    }
+    Ok(format!("{}", count))
+  }
+
+  fn part2(chans: &HashMap<i64, Chan>) -> Result<String> {
+    let buckets = build_groups(chans);
+    let mut group_ids = HashSet::new();
+    for n in chans.keys() {
+      if let Some(group_ref) = buckets.get(n) {
+        let val = rc_to_usize(group_ref);
+        group_ids.insert(val);
+      }
+    }
+    Ok(format!("{}", group_ids.len()))
+  }
+}
@@ -0,0 +1,99 @@
+use aoc_common::{Result, Solution, invalid_input, to_i64};
+
+
+#[derive(Debug)]
+pub struct Scanner {
+  depth: i64,
+  range: i64,
+  period: i64,
+}
+
+impl Scanner {
+  fn new(depth: i64, range: i64) -> Scanner {
+    Scanner {
+      depth,
+      range,
+      period: (range-1) * 2,
+    }
+  }
+
+  fn is_at0(&self, time: i64) -> bool {
+    (time % self.period) == 0
+  }
+
+  fn severity(&self) -> i64 {
+    self.depth * self.range
+  }
+}
+
+fn find_max_depth(scanners: &[Scanner]) -> i64 {
+  let mut max = i64::MIN;
+  for scanner in scanners {
+    max = i64::max(max, scanner.depth);
+  }
+  max
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+  type Input = Vec<Scanner>;
+
+  fn parse(data: &str) -> Result<Vec<Scanner>> {
+    let mut res = Vec::new();
+    for line in data.lines() {
+      let mut toks =
+          line.split_whitespace()
+              .map(|s| s.chars().filter(|c| c.is_ascii_digit()).collect::<String>())
+              .map(|s| to_i64(&s));
+      let depth = toks.next().ok_or(invalid_input!("Missing depth: {}", line))??;
+      let range = toks.next().ok_or(invalid_input!("Missing range: {}", line))??;
+      res.push(Scanner::new(depth, range));
+    }
+    res.sort_by_key(|a| a.depth);
+    Ok(res)
+  }
+
+  fn part1(scanners: &Vec<Scanner>) -> Result<String> {
+    let max_depth = find_max_depth(scanners) + 1;
+    let mut total = 0;
+    let mut idx = 0;
+    for i in 0..max_depth {
+      if scanners[idx].depth != i {
+        continue;
+      }
+      let j = idx;
+      idx += 1;
+      if !scanners[j].is_at0(i) {
+        continue;
+      }
+      total += scanners[j].severity();
+    }
+    Ok(format!("{}", total))
+  }
+
+  fn part2(scanners: &Vec<Scanner>) -> Result<String> {
+    let max_depth = find_max_depth(scanners) + 1;
+    let mut start = 0;
+    loop {
+      let mut caught = false;
+      let mut idx = 0;
+      for i in start..(start + max_depth) {
+        if scanners[idx].depth != (i - start) {
+          continue;
+        }
+        let j = idx;
+        idx += 1;
+        if scanners[j].is_at0(i) {
+          caught = true;
+          start += 1;
+          break;
+        }
+      }
+      if !caught {
+        break;
+      }
+    }
+    Ok(format!("{}", start))
+  }
+}
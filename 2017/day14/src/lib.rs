@@ -1,38 +1,16 @@
-#[macro_use] extern crate failure;
-
+use aoc_common::{Result, Solution, invalid_input};
 use std::cell::RefCell;
 use std::collections::{HashSet, VecDeque};
 use std::fmt;
 use std::fmt::{Display, Write};
-use std::fs::File;
-use std::i64;
-use std::io::Read;
-use std::path::{Path, PathBuf};
 use std::ops::Deref;
 use std::rc::Rc;
-use std::result;
 
-type Result<T> = result::Result<T, failure::Error>;
 
 const LIST_SIZE: usize = 256;
 
-fn read_data_file<P>(filename: P) -> Result<String>
-    where P: AsRef<Path> {
-  let mut f = File::open(&filename)?;
-  let mut data = String::new();
-  f.read_to_string(&mut data)?;
-  let line = data.lines()
-                 .next()
-                 .ok_or(format_err!("No input line"))?;
-  Ok(line.to_owned())
-}
-
-fn xor_combine(xs: &Vec<u8>, start: usize, len: usize) -> u8 {
-  let mut n = xs[start];
-  for i in (start+1)..(start+len) {
-    n = n ^ xs[i];
-  }
-  n
+fn xor_combine(xs: &[u8], start: usize, len: usize) -> u8 {
+  xs[start..(start+len)].iter().fold(0, |acc, b| acc ^ b)
 }
 
 struct Circular {
@@ -110,7 +88,19 @@ fn knot_hash(input: &str) -> Result<String> {
     }
   }
   rope.densify();
-  Ok(rope.hex_digest()?)
+  rope.hex_digest()
+}
+
+fn count_bits(hex_str: &str) -> Result<i64> {
+  let mut total = 0;
+  for i in 0..hex_str.len() {
+    let n = i64::from_str_radix(&hex_str[i..(i+1)], 16)?;
+    total += (n >> 3) & 1;
+    total += (n >> 2) & 1;
+    total += (n >> 1) & 1;
+    total += n & 1;
+  }
+  Ok(total)
 }
 
 fn to_bit_vec(hex_str: &str) -> Result<Vec<i64>> {
@@ -134,8 +124,8 @@ struct Coord {
 impl Coord {
   fn new(x: i64, y: i64) -> Coord {
     Coord {
-      x: x,
-      y: y,
+      x,
+      y,
     }
   }
 
@@ -163,7 +153,7 @@ impl Coord {
 
 const DIRS: &[(i64, i64)] = &[(1, 0), (-1, 0), (0, 1), (0, -1)];
 
-fn mark_group(bit_arr: &Vec<RefCell<Vec<i64>>>, i: i64, j: i64, id: i64) {
+fn mark_group(bit_arr: &[RefCell<Vec<i64>>], i: i64, j: i64, id: i64) {
   let mut q = VecDeque::new();
   let mut hs = HashSet::new();
   {
@@ -191,30 +181,43 @@ fn mark_group(bit_arr: &Vec<RefCell<Vec<i64>>>, i: i64, j: i64, id: i64) {
   }
 }
 
-fn solve<P>(input: P) -> Result<String>
-    where P: AsRef<Path> {
-  let data = read_data_file(input)?;
-  let mut bit_arr: Vec<RefCell<Vec<i64>>> = Vec::with_capacity(128);
-  for i in 0..128 {
-    let hash = knot_hash(&format!("{}-{}", data, i))?;
-    bit_arr.push(RefCell::new(to_bit_vec(&hash)?));
-  }
-  let mut next_id = 1;
-  for i in 0..128 {
-    for j in 0..128 {
-      let cell_val = { bit_arr[i].borrow()[j] };
-      if cell_val == i64::MAX {
-        mark_group(&bit_arr, i as i64, j as i64, next_id);
-        next_id += 1;
-      }
+pub struct Day14;
+
+impl Solution for Day14 {
+  type Input = String;
+
+  fn parse(data: &str) -> Result<String> {
+    let line = data.lines()
+                   .next()
+                   .ok_or(invalid_input!("No input line"))?;
+    Ok(line.to_owned())
+  }
+
+  fn part1(data: &String) -> Result<String> {
+    let mut total = 0;
+    for i in 0..128 {
+      let hash = knot_hash(&format!("{}-{}", data, i))?;
+      total += count_bits(&hash)?;
     }
+    Ok(format!("{}", total))
   }
-  Ok(format!("{}", next_id - 1))
-}
 
-fn main() {
-  match solve(PathBuf::from("input.txt")) {
-    Ok(msg) => println!("Result: {}", msg),
-    Err(err) => println!("Error: {}", err),
+  fn part2(data: &String) -> Result<String> {
+    let mut bit_arr: Vec<RefCell<Vec<i64>>> = Vec::with_capacity(128);
+    for i in 0..128 {
+      let hash = knot_hash(&format!("{}-{}", data, i))?;
+      bit_arr.push(RefCell::new(to_bit_vec(&hash)?));
+    }
+    let mut next_id = 1;
+    for i in 0..128 {
+      for j in 0..128 {
+        let cell_val = { bit_arr[i].borrow()[j] };
+        if cell_val == i64::MAX {
+          mark_group(&bit_arr, i as i64, j as i64, next_id);
+          next_id += 1;
+        }
+      }
+    }
+    Ok(format!("{}", next_id - 1))
   }
 }
@@ -0,0 +1,67 @@
+use aoc_common::{Result, Solution, invalid_input, parse_lines, to_i64};
+
+
+// Parses "Generator A starts with 591" into the name and the seed.
+fn parse_seed(line: &str) -> Result<(String, i64)> {
+  let toks = line.split_whitespace().collect::<Vec<_>>();
+  if toks.len() != 5 || toks[0] != "Generator" {
+    return Err(invalid_input!("Expected a generator seed"));
+  }
+  Ok((toks[1].to_owned(), to_i64(toks[4])?))
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+  type Input = (i64, i64);
+
+  fn parse(data: &str) -> Result<(i64, i64)> {
+    let seeds = parse_lines(data, parse_seed)?;
+    match seeds.as_slice() {
+      [(a, seed_a), (b, seed_b)] if a == "A" && b == "B" => Ok((*seed_a, *seed_b)),
+      _ => Err(invalid_input!("Expected seeds for generators A and B")),
+    }
+  }
+
+  fn part1(&(seed_a, seed_b): &(i64, i64)) -> Result<String> {
+    let mut a = seed_a;
+    let af: i64 = 16807;
+    let mut b = seed_b;
+    let bf: i64 = 48271;
+    let mut count = 0;
+    for _ in 0..40_000_001 {
+      a = (a * af) % 2147483647;
+      b = (b * bf) % 2147483647;
+      if (a & 0xFFFF) == (b & 0xFFFF) {
+        count += 1;
+      }
+    }
+    Ok(format!("{}", count))
+  }
+
+  fn part2(&(seed_a, seed_b): &(i64, i64)) -> Result<String> {
+    let mut a = seed_a;
+    let af: i64 = 16807;
+    let mut b = seed_b;
+    let bf: i64 = 48271;
+    let mut count = 0;
+    for _ in 0..5_000_001 {
+      loop {
+        a = (a * af) % 2147483647;
+        if (a % 4) == 0 {
+          break
+        }
+      }
+      loop {
+        b = (b * bf) % 2147483647;
+        if (b % 8) == 0 {
+          break;
+        }
+      }
+      if (a & 0xFFFF) == (b & 0xFFFF) {
+        count += 1;
+      }
+    }
+    Ok(format!("{}", count))
+  }
+}
@@ -0,0 +1,143 @@
+use aoc_common::{Result, Solution, column_of, invalid_input, to_i64};
+use std::collections::VecDeque;
+
+
+const MAX_CHAR_IDX: u8 = 16;
+
+#[derive(Debug)]
+pub enum Move {
+  Spin{n: i64},
+  Exchange{a: i64, b: i64},
+  Partner{a: char, b: char},
+}
+
+impl Move {
+  fn parse(s: &str) -> Result<Move> {
+    match s.chars().next() {
+      Some('s') => Move::parse_spin(s),
+      Some('x') => Move::parse_exchange(s),
+      Some('p') => Move::parse_partner(s),
+      _ => Err(invalid_input!("Unknown Move: {}", s)),
+    }
+  }
+
+  fn parse_spin(s: &str) -> Result<Move> {
+    let n = to_i64(&s[1..s.len()])?;
+    if n < 0 || n > MAX_CHAR_IDX as i64 {
+      Err(invalid_input!("Spin out of range: {}", s))
+    } else {
+      Ok(Move::Spin {
+        n,
+      })
+    }
+  }
+
+  fn parse_exchange(s: &str) -> Result<Move> {
+    let mut it = s[1..s.len()].split("/").map(to_i64);
+    let a = it.next().ok_or(invalid_input!("Missing Exchange A: {}", s))??;
+    let b = it.next().ok_or(invalid_input!("Missing Exchange B: {}", s))??;
+    if [a, b].iter().any(|&n| n < 0 || n >= MAX_CHAR_IDX as i64) {
+      Err(invalid_input!("Exchange out of range: {}", s))
+    } else {
+      Ok(Move::Exchange{a, b})
+    }
+  }
+
+  fn parse_partner(s: &str) -> Result<Move> {
+    let mut it = s[1..s.len()].split("/");
+    let a = it.next().ok_or(invalid_input!("Missing Partner A: {}", s))?
+              .chars()
+              .next().ok_or(invalid_input!("Zero length Partner A: {}", s))?;
+    let b = it.next().ok_or(invalid_input!("Missing Partner B: {}", s))?
+              .chars()
+              .next().ok_or(invalid_input!("Zero length Partner B: {}", s))?;
+    let last = (b'a' + MAX_CHAR_IDX - 1) as char;
+    if [a, b].iter().any(|&c| !('a'..=last).contains(&c)) {
+      Err(invalid_input!("Partner out of range: {}", s))
+    } else {
+      Ok(Move::Partner{a, b})
+    }
+  }
+
+  fn run(&self, programs: &mut VecDeque<char>) -> Result<()> {
+    match *self {
+      Move::Spin{n} => self.run_spin(programs, n),
+      Move::Exchange{a, b} => self.run_exchange(programs, a, b),
+      Move::Partner{a, b} => self.run_partner(programs, a, b),
+    }
+  }
+
+  fn run_spin(&self, programs: &mut VecDeque<char>, n: i64) -> Result<()> {
+    for _ in 0..n {
+      let m = programs.pop_back().ok_or(invalid_input!("Empty programs"))?;
+      programs.push_front(m);
+    }
+    Ok(())
+  }
+
+  fn run_exchange(&self, programs: &mut VecDeque<char>, a: i64, b: i64) -> Result<()> {
+    programs.swap(a as usize, b as usize);
+    Ok(())
+  }
+
+  fn run_partner(&self, programs: &mut VecDeque<char>, a: char, b: char) -> Result<()> {
+    let ai = programs.iter().take_while(|c| **c != a).fold(0, |acc, &_c| acc + 1);
+    let bi = programs.iter().take_while(|c| **c != b).fold(0, |acc, &_c| acc + 1);
+    programs.swap(ai, bi);
+    Ok(())
+  }
+}
+
+fn new_programs() -> VecDeque<char> {
+  (0..MAX_CHAR_IDX).map(|n| (n + b'a') as char)
+                   .collect::<VecDeque<char>>()
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+  type Input = Vec<Move>;
+
+  fn parse(data: &str) -> Result<Vec<Move>> {
+    let line = data.lines()
+                   .next().ok_or(invalid_input!("No input lines found"))?;
+    // The dance is one long line, so point at the offending move instead.
+    line.split(',')
+        .map(|s| Move::parse(s).map_err(|err| err.at(1, column_of(line, s), s)))
+        .collect::<Result<Vec<_>>>()
+  }
+
+  fn part1(instructions: &Vec<Move>) -> Result<String> {
+    let mut programs = new_programs();
+    for instruction in instructions.iter() {
+      instruction.run(&mut programs)?;
+    }
+    Ok(programs.iter().collect::<String>())
+  }
+
+  fn part2(instructions: &Vec<Move>) -> Result<String> {
+    let start = new_programs();
+    let start_str = start.iter().collect::<String>();
+    let mut programs = start.clone();
+    let mut count = 0;
+    loop {
+      count += 1;
+      for instruction in instructions.iter() {
+        instruction.run(&mut programs)?;
+      }
+      let r = programs.iter().collect::<String>();
+      if r == start_str {
+        break;
+      }
+    }
+    let count = count;
+    let remainder = 1_000_000_000 % count;
+    programs = start.clone();
+    for _ in 0..remainder {
+      for instruction in instructions.iter() {
+        instruction.run(&mut programs)?;
+      }
+    }
+    Ok(programs.iter().collect::<String>())
+  }
+}
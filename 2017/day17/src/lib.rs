@@ -0,0 +1,47 @@
+use aoc_common::{Result, Solution, invalid_input, to_usize};
+use std::collections::LinkedList;
+
+
+pub struct Day17;
+
+impl Solution for Day17 {
+  type Input = usize;
+
+  fn parse(data: &str) -> Result<usize> {
+    to_usize(data.lines()
+                 .next().ok_or(invalid_input!("No input lines found"))?)
+  }
+
+  fn part1(&cycle: &usize) -> Result<String> {
+    let mut idx = 0;
+    let mut lst = LinkedList::new();
+    lst.push_back(0);
+    for i in 1..2018 {
+      idx = (idx + cycle) % lst.len() + 1;
+      if idx >= lst.len() {
+        lst.push_back(i);
+      } else {
+        let mut tmplst = lst.split_off(idx);
+        lst.push_back(i);
+        lst.append(&mut tmplst);
+      }
+    }
+    let idx = (idx+2) % lst.len();
+    lst.split_off(idx);
+    let res = lst.pop_back().ok_or(invalid_input!("List empty, uh..."))?;
+    Ok(format!("{}", res))
+  }
+
+  fn part2(&cycle: &usize) -> Result<String> {
+    let mut n_at_1 = 0;
+    let mut idx = 0;
+    for i in 1..50_000_001 {
+      // The buffer holds exactly i values before inserting i.
+      idx = (idx + cycle) % i + 1;
+      if idx == 1 {
+        n_at_1 = i;
+      }
+    }
+    Ok(format!("{}", n_at_1))
+  }
+}
@@ -0,0 +1,10 @@
+use aoc_common::Result;
+use aoc_vm::{Policy, Program, Scheduler, Topology};
+
+
+/// Runs two copies of the program against each other until both wait on an
+/// empty queue, and returns how many values program 1 sent.
+pub fn count_sends(program: &Program) -> Result<usize> {
+  let report = Scheduler::spawn(program, 2, Topology::Pairwise, Policy::RunUntilBlock)?.run()?;
+  Ok(report.procs[1].sent)
+}
@@ -0,0 +1,36 @@
+use aoc_common::{Result, Solution};
+use aoc_vm::{DUET, Program, SOUND};
+
+mod duet;
+mod sound;
+
+
+/// The same listing read as each part's dialect, since they disagree on what
+/// `snd` and `rcv` mean.
+#[derive(Debug)]
+pub struct Programs {
+  pub sound: Program,
+  pub duet: Program,
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+  type Input = Programs;
+
+  fn parse(data: &str) -> Result<Programs> {
+    Ok(Programs { sound: SOUND.compile(data)?, duet: DUET.compile(data)? })
+  }
+
+  // snd plays a sound and rcv recovers the last one played.
+  fn part1(programs: &Programs) -> Result<String> {
+    let res = sound::recover_first(&programs.sound)?;
+    Ok(format!("{}", res))
+  }
+
+  // snd and rcv pass messages between two copies of the program.
+  fn part2(programs: &Programs) -> Result<String> {
+    let res = duet::count_sends(&programs.duet)?;
+    Ok(format!("{}", res))
+  }
+}
@@ -0,0 +1,22 @@
+use aoc_common::{Result, invalid_input, no_solution};
+use aoc_vm::{CPU, Program, Step};
+
+
+/// Runs the program until the first rcv with a non-zero value and returns
+/// the sound it recovers.
+pub fn recover_first(program: &Program) -> Result<i64> {
+  let mut cpu = CPU::new(program);
+  let mut played = None;
+  loop {
+    match cpu.run()? {
+      Step::Sent(n) => played = Some(n),
+      Step::Recovered => {
+        return played.ok_or_else(|| no_solution!("Recovered before any sound was played"));
+      },
+      Step::Halted => {
+        return Err(no_solution!("Program ended at {} without recovering", cpu.pc()));
+      },
+      step => return Err(invalid_input!("Unexpected {:?} at {}", step, cpu.pc())),
+    }
+  }
+}
@@ -0,0 +1,112 @@
+use aoc_common::{Result, Solution, char_grid, invalid_input};
+
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Vector {
+  x: i64,
+  y: i64,
+}
+
+impl Vector {
+  fn new(x: i64, y: i64) -> Vector {
+    Vector { x, y }
+  }
+
+  fn add(&self, other: &Vector) -> Vector {
+    Vector::new(self.x + other.x, self.y + other.y)
+  }
+
+  fn neg(&self) -> Vector {
+    Vector::new(-self.x, -self.y)
+  }
+}
+
+fn find_start_loc(data: &[Vec<char>]) -> Result<Vector> {
+  for (i, ch) in data[0].iter().enumerate() {
+    if *ch == '|' {
+      return Ok(Vector::new(0, i as i64));
+    }
+  }
+  Err(invalid_input!("Char | not found"))
+}
+
+// Anything off the edge of the diagram is empty space.
+fn char_at(data: &[Vec<char>], idx: &Vector) -> char {
+  if idx.x < 0 || idx.y < 0 {
+    return ' ';
+  }
+  data.get(idx.x as usize)
+      .and_then(|row| row.get(idx.y as usize))
+      .map_or(' ', |ch| *ch)
+}
+
+static DIRS: &[Vector] = &[Vector{x:1, y:0}, Vector{x:-1, y:0}, Vector{x:0, y:1}, Vector{x:0, y:-1}, ];
+
+fn redirect(data: &[Vec<char>], idx: &Vector, dir: &Vector) -> Result<Vector> {
+  let nir = dir.neg();
+  for new_dir in DIRS {
+    let ch = char_at(data, &idx.add(new_dir));
+    if (ch == '|' || ch == '-' || ch.is_alphabetic()) && *new_dir != nir {
+      return Ok(*new_dir);
+    }
+  }
+  Err(invalid_input!("Unable to determine new direction: {:?} {:?}", idx, dir))
+}
+
+// Follows the path from the top edge, returning the letters passed along the
+// way and the number of steps taken.
+fn walk(data: &[Vec<char>]) -> Result<(String, usize)> {
+  let mut idx = find_start_loc(data)?;
+  let mut dir = Vector::new(1, 0);
+  let mut letters = Vec::new();
+  let mut steps = 0;
+  let mut retry = true;
+  loop {
+    idx = idx.add(&dir);
+    steps += 1;
+    if idx.x < 0 || idx.y < 0 || idx.x >= data.len() as i64 || idx.y >= data[0].len() as i64 {
+      // I guess it's done?
+      steps -= 1;
+      break;
+    }
+    let ch = data[idx.x as usize][idx.y as usize];
+    if ch == ' ' {
+      if !retry {
+        // I guess it's done?
+        break;
+      } else {
+         retry = false;
+      } 
+      continue;
+    } else {
+      retry = true;
+    }
+
+    if ch.is_alphabetic() {
+      letters.push(ch);
+    } else if ch == '+' {
+      dir = redirect(data, &idx, &dir)?;
+    }
+  }
+  Ok((letters.iter().collect::<String>(), steps))
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+  type Input = Vec<Vec<char>>;
+
+  fn parse(data: &str) -> Result<Vec<Vec<char>>> {
+    Ok(char_grid(data))
+  }
+
+  fn part1(grid: &Vec<Vec<char>>) -> Result<String> {
+    let (letters, _) = walk(grid)?;
+    Ok(letters)
+  }
+
+  fn part2(grid: &Vec<Vec<char>>) -> Result<String> {
+    let (_, steps) = walk(grid)?;
+    Ok(format!("{}", steps))
+  }
+}
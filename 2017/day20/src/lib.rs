@@ -0,0 +1,170 @@
+use aoc_common::{Result, Solution, invalid_input, parse_lines, to_i64};
+use regex::Regex;
+use std::collections::HashMap;
+
+
+const SETTLING_COUNT: i64 = 10000;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Vector {
+  x: i64,
+  y: i64,
+  z: i64,
+}
+
+impl Vector {
+  fn new(x: i64, y: i64, z: i64) -> Vector {
+    Vector { x, y, z }
+  }
+
+  fn dist(&self) -> i64 {
+    i64::abs(self.x) + i64::abs(self.y) + i64::abs(self.z)
+  }
+
+  fn add(&mut self, other: &Vector) {
+    self.x += other.x;
+    self.y += other.y;
+    self.z += other.z;
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct Point {
+  loc: Vector,
+  vel: Vector,
+  acc: Vector,
+  deleted: bool,
+}
+
+impl Point {
+  fn new(loc: Vector, vel: Vector, acc: Vector) -> Point {
+    Point { loc, vel, acc, deleted: false }
+  }
+
+  fn from_captures(captures: &regex::Captures) -> Result<Point> {
+    if captures.len() < 10 {
+      return Err(invalid_input!("Insufficient captures: {}", captures.len()));
+    }
+    Ok(Point::new(
+      Vector::new(
+        to_i64(captures.get(1).map_or("bad1", |m| m.as_str()))?,
+        to_i64(captures.get(2).map_or("bad2", |m| m.as_str()))?,
+        to_i64(captures.get(3).map_or("bad3", |m| m.as_str()))?),
+      Vector::new(
+        to_i64(captures.get(4).map_or("bad4", |m| m.as_str()))?,
+        to_i64(captures.get(5).map_or("bad5", |m| m.as_str()))?,
+        to_i64(captures.get(6).map_or("bad6", |m| m.as_str()))?),
+      Vector::new(
+        to_i64(captures.get(7).map_or("bad7", |m| m.as_str()))?,
+        to_i64(captures.get(8).map_or("bad8", |m| m.as_str()))?,
+        to_i64(captures.get(9).map_or("bad9", |m| m.as_str()))?)))
+  }
+
+  fn advance(&mut self) {
+    self.vel.add(&self.acc);
+    self.loc.add(&self.vel);
+  }
+
+  fn is_deleted(&self) -> bool {
+    self.deleted
+  }
+
+  fn set_deleted(&mut self) {
+    self.deleted = true;
+  }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+  type Input = Vec<Point>;
+
+  fn parse(data: &str) -> Result<Vec<Point>> {
+    let re = Regex::new(r"p=<\s*(-?\d+),\s*(-?\d+),\s*(-?\d+)>, v=<\s*(-?\d+),\s*(-?\d+),\s*(-?\d+)>, a=<\s*(-?\d+),\s*(-?\d+),\s*(-?\d+)>").unwrap();
+    parse_lines(data, |line| {
+      let cap = re.captures(line).ok_or_else(|| invalid_input!("Not a particle"))?;
+      Point::from_captures(&cap)
+    })
+  }
+
+  fn part1(points: &Vec<Point>) -> Result<String> {
+    let mut pts = points.clone();
+    let mut closest_id = i64::MAX;
+  // Reddit's math answer.
+  // This isn't quite right as it doesn't break ties due to starting velocity. I
+  // didn't need to break ties with my input though.
+  //  let mut closest = i64::MAX;
+  //  for i in 0..pts.len() {
+  //    let pt = &mut pts[i];
+  //    let acc = pt.acc.dist();
+  //    if acc <= closest {
+  //      closest = acc;
+  //      closest_id = i as i64;
+  //    }
+  //  }
+  //  Ok(format!("{}", closest_id))
+
+    let mut count = SETTLING_COUNT;
+    loop {
+      let mut closest_dist = i64::MAX;
+      let mut now_closest_id = i64::MAX;
+      for (i, pt) in pts.iter_mut().enumerate() {
+        pt.advance();
+        let dist = pt.loc.dist();
+        if dist < closest_dist {
+          closest_dist = dist;
+          now_closest_id = i as i64;
+        }
+      }
+      if closest_id == now_closest_id {
+        count -= 1;
+        if count <= 0 {
+          break;
+        }
+      } else {
+        count = SETTLING_COUNT;
+        closest_id = now_closest_id;
+      }
+    }
+    Ok(format!("{}", closest_id))
+  }
+
+  fn part2(points: &Vec<Point>) -> Result<String> {
+    let mut pts = points.clone();
+    let mut closest_id = i64::MAX;
+    let mut count = SETTLING_COUNT;
+    loop {
+      let mut collissions: HashMap<Vector, Vec<usize>> = HashMap::new();
+      let mut closest_dist = i64::MAX;
+      let mut now_closest_id = i64::MAX;
+      for (i, pt) in pts.iter_mut().enumerate() {
+        if pt.is_deleted() {
+          continue;
+        }
+        pt.advance();
+        collissions.entry(pt.loc).or_default().push(i);
+        let dist = pt.loc.dist();
+        if dist < closest_dist {
+          closest_dist = dist;
+          now_closest_id = i as i64;
+        }
+      }
+      if closest_id == now_closest_id {
+        count -= 1;
+        if count <= 0 {
+          break;
+        }
+      } else {
+        count = SETTLING_COUNT;
+        closest_id = now_closest_id;
+      }
+      for vs in collissions.values().filter(|v| v.len() > 1) {
+        for idx in vs {
+          pts[*idx].set_deleted();
+        }
+      }
+    }
+    let count = pts.iter().filter(|pt| !pt.is_deleted()).count();
+    Ok(format!("{}", count))
+  }
+}
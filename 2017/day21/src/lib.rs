@@ -1,19 +1,14 @@
-#[macro_use] extern crate failure;
-
+use aoc_common::{AocError, Result, Solution, invalid_input, no_solution};
 use std::collections::HashMap;
-use std::fs::File;
 use std::hash::{Hash, Hasher};
-use std::io::Read;
-use std::path::{Path, PathBuf};
-use std::result;
 use std::str::FromStr;
-use std::u16;
 
-type Result<T> = result::Result<T, failure::Error>;
 
 trait BitMagic {
   type Me;
+  #[allow(dead_code)]
   fn rotate_ccw_1(&self, dim: usize) -> Result<Self::Me>;
+  #[allow(dead_code)]
   fn rotate_ccw(&self, dim: usize) -> Result<Self::Me>;
   fn rotate_cw_1(&self, dim: usize) -> Result<Self::Me>;
   fn rotate_cw(&self, dim: usize) -> Result<Self::Me>;
@@ -30,7 +25,6 @@ fn bit_coord(dim: usize, idx: usize) -> (usize, usize) {
   (idx % dim, idx / dim)
 }
 
-
 // Positive iteration is counter-clockwise.
 const ROTATE2X2: &[(usize, usize)] = &[(0, 0), (1, 0), (1, 1), (0, 1)];
 const ROTATE3X3: &[(usize, usize)] = &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (1, 2), (0, 2), (0, 1)];
@@ -41,8 +35,8 @@ impl BitMagic for u16 {
   fn rotate_ccw_1(&self, dim: usize) -> Result<Self::Me> {
     let n = *self;
     let mut new_n = n;
-    if dim < 2 || dim > 3 {
-      return Err(format_err!("Unsupported dim: {}", dim));
+    if !(2..=3).contains(&dim) {
+      return Err(invalid_input!("Unsupported dim: {}", dim));
     }
     let rotations = match dim {
       2 => ROTATE2X2,
@@ -53,14 +47,14 @@ impl BitMagic for u16 {
       let tmp = rotations[rotations.len() - 1];
       (n >> bit_index(dim, tmp.0, tmp.1)) & 0x1
     };
-    for i in 0..rotations.len() {
-      let bit_idx = bit_index(dim, rotations[i].0, rotations[i].1);
+    for rot in rotations {
+      let bit_idx = bit_index(dim, rot.0, rot.1);
       let cur_val = next_val;
       next_val = (n >> bit_idx) & 0x1;
       if cur_val == 1 {
-        new_n = new_n | (cur_val << bit_idx);
+        new_n |= cur_val << bit_idx;
       } else {
-        new_n = new_n & !(1 << bit_idx);
+        new_n &= !(1 << bit_idx);
       }
     }
     Ok(new_n)
@@ -70,15 +64,15 @@ impl BitMagic for u16 {
     match dim {
       2 => self.rotate_ccw_1(dim),
       3 => self.rotate_ccw_1(dim)?.rotate_ccw_1(dim),
-      _ => Err(format_err!("Unsupported dim: {}", dim)),
+      _ => Err(invalid_input!("Unsupported dim: {}", dim)),
     }
   }
 
   fn rotate_cw_1(&self, dim: usize) -> Result<Self::Me> {
     let n = *self;
     let mut new_n = n;
-    if dim < 2 || dim > 3 {
-      return Err(format_err!("Unsupported dim: {}", dim));
+    if !(2..=3).contains(&dim) {
+      return Err(invalid_input!("Unsupported dim: {}", dim));
     }
     let rotations = match dim {
       2 => ROTATE2X2,
@@ -96,9 +90,9 @@ impl BitMagic for u16 {
       let cur_val = next_val;
       next_val = (n >> bit_idx) & 0x1;
       if cur_val == 1 {
-        new_n = new_n | (cur_val << bit_idx);
+        new_n |= cur_val << bit_idx;
       } else {
-        new_n = new_n & !(1 << bit_idx);
+        new_n &= !(1 << bit_idx);
       }
     }
     Ok(new_n)
@@ -108,7 +102,7 @@ impl BitMagic for u16 {
     match dim {
       2 => self.rotate_cw_1(dim),
       3 => self.rotate_cw_1(dim)?.rotate_cw_1(dim),
-      _ => Err(format_err!("Unsupported dim: {}", dim)),
+      _ => Err(invalid_input!("Unsupported dim: {}", dim)),
     }
   }
 
@@ -119,7 +113,7 @@ impl BitMagic for u16 {
     } else if dim == 3 {
       Ok(((n & 0b100100100) >> 2) | (n & 0b010010010) | ((n & 0b001001001) << 2))
     } else {
-      Err(format_err!("Undefined dimention: {}", dim))
+      Err(invalid_input!("Undefined dimention: {}", dim))
     }
   }
 
@@ -130,7 +124,7 @@ impl BitMagic for u16 {
     } else if dim == 3 {
       Ok(((n & 0b111) << 6) | (n & 0b111000) | ((n >> 6) & 0b111))
     } else {
-      Err(format_err!("Undefined dimention: {}", dim))
+      Err(invalid_input!("Undefined dimention: {}", dim))
     }
   }
 
@@ -153,42 +147,37 @@ impl BitMagic for u16 {
         rot = rot.rotate_cw(3)?;
       }
     } else {
-      return Err(format_err!("Undefined dimention: {}", dim));
+      return Err(invalid_input!("Undefined dimention: {}", dim));
     }
     Ok(min)
   }
 }
 
 #[derive(Clone, Debug)]
-struct Grid {
+pub struct Grid {
   dim: usize,
   data: Vec<bool>,
 }
 
 impl Grid {
   fn new(dim: usize) -> Grid {
-    let len = dim.pow(2);
-    let mut vec = Vec::with_capacity(len);
-    for _ in 0..len {
-      vec.push(false);
-    }
     Grid {
-      dim: dim,
-      data: vec,
+      dim,
+      data: vec![false; dim.pow(2)],
     }
   }
 
   fn from_subgrids(subs: Vec<Grid>) -> Result<Grid> {
     if subs.is_empty() {
-      return Err(format_err!("Insufficient sub-grids: {}", subs.len()));
+      return Err(invalid_input!("Insufficient sub-grids: {}", subs.len()));
     }
     let dim = (subs.len() as f64).sqrt() as usize;
     if dim.pow(2) != subs.len() {
-      return Err(format_err!("Insufficient sub-grids: {}", subs.len()));
+      return Err(invalid_input!("Insufficient sub-grids: {}", subs.len()));
     }
     let sub_dim = subs[0].dim;
     if subs.iter().any(|sg| sg.dim != sub_dim) {
-      return Err(format_err!("Mismatched sub-grid DIM"));
+      return Err(invalid_input!("Mismatched sub-grid DIM"));
     }
     let mut grid = Grid::new(dim * sub_dim);
     for (sg_idx, sg) in subs.iter().enumerate() {
@@ -204,13 +193,13 @@ impl Grid {
     Ok(grid)
   }
 
-  fn iter(&self, slice_dim: usize) -> Result<GridIterator> {
-    if self.dim % slice_dim != 0 {
-      return Err(format_err!("Grid dimention not a multiple of the slice size"));
+  fn iter(&self, slice_dim: usize) -> Result<GridIterator<'_>> {
+    if !self.dim.is_multiple_of(slice_dim) {
+      return Err(invalid_input!("Grid dimention not a multiple of the slice size"));
     }
     Ok(GridIterator {
       grid: self,
-      slice_dim: slice_dim,
+      slice_dim,
       max_d: self.dim / slice_dim,
       dx: 0,
       dy: 0,
@@ -219,23 +208,23 @@ impl Grid {
 
   fn to_n(&self) -> Result<u16> {
     if self.dim < 2 || self.dim > 4 {
-      return Err(format_err!("Unable to translate grid of size {} to N", self.dim));
+      return Err(invalid_input!("Unable to translate grid of size {} to N", self.dim));
     }
     let mut n = 0;
     for (i, b) in self.data.iter().enumerate() {
       if *b {
-        n = n | (1 << i);
+        n |= 1 << i;
       }
       if i >= 16 {
-        return Err(format_err!("Too many elements: {}", i));
+        return Err(invalid_input!("Too many elements: {}", i));
       }
     }
-    Ok(n.normalize(self.dim)?)
+    n.normalize(self.dim)
   }
 
   fn to_n_unsafe(&self) -> u16 {
     match self.to_n() {
-      Ok(n) => return n,
+      Ok(n) => n,
       Err(err) => panic!("to_n_unsafe error: {}", err),
     }
   }
@@ -243,7 +232,7 @@ impl Grid {
   fn from_line(s: &str) -> Result<(Self, Self)> {
     let parts = s.trim().split(" => ").map(ToOwned::to_owned).collect::<Vec<String>>();
     if parts.len() != 2 {
-      return Err(format_err!("Invalid pattern line: {}", s));
+      return Err(invalid_input!("Invalid pattern line: {}", s));
     }
     let grid_from = Grid::from_str(&parts[0])?;
     let grid_to = Grid::from_str(&parts[1])?;
@@ -252,13 +241,13 @@ impl Grid {
 }
 
 impl FromStr for Grid {
-  type Err = failure::Error;
+  type Err = AocError;
 
   fn from_str(s: &str) -> Result<Self> {
     let n_from = s.chars().filter(|ch| *ch == '#' || *ch == '.').count();
     let div_from = (n_from as f64).sqrt().round() as usize;
     if div_from.pow(2) != n_from {
-      return Err(format_err!("Grid size not a square dim: {}", s));
+      return Err(invalid_input!("Grid size not a square dim: {}", s));
     }
     let mut grid = Grid::new(div_from);
     for (i, ch) in s.chars().filter(|ch| *ch == '#' || *ch == '.').enumerate() {
@@ -323,40 +312,23 @@ impl<'a> Iterator for GridIterator<'a> {
   }
 }
 
-type PatternMap = HashMap<Grid, Grid>;
-
-fn read_data_file<P>(filename: P) -> Result<PatternMap>
-    where P: AsRef<Path> {
-  let mut f = File::open(&filename)?;
-  let mut data = String::new();
-  f.read_to_string(&mut data)?;
-  let mut res = HashMap::new();
-  for line in data.lines() {
-    let (grid_from, grid_to) = Grid::from_line(line)?;
-    if res.contains_key(&grid_from) {
-      return Err(format_err!("Duplicate from Grid in input: {}", line));
-    }
-    res.insert(grid_from, grid_to);
-  }
-  Ok(res)
-}
+pub type PatternMap = HashMap<Grid, Grid>;
 
-fn solve<P>(input: P) -> Result<String>
-    where P: AsRef<Path> {
-  let translations = read_data_file(input)?;
+// Runs the enhancement rules and counts the pixels left on.
+fn enhance(translations: &PatternMap, iterations: usize) -> Result<String> {
   let mut grid = Grid::from_str(".#./..#/###")?;
-  for _ in 0..5 {
+  for _ in 0..iterations {
     let mut vec = Vec::new();
     let n = if grid.dim % 2 == 0 {
       Ok(2)
     } else if grid.dim % 3 == 0 {
       Ok(3)
     } else {
-      Err(format_err!("Bad multiple: {}", grid.dim))
+      Err(invalid_input!("Bad multiple: {}", grid.dim))
     }?;
     for sgr in grid.iter(n)? {
       if !translations.contains_key(&sgr) {
-        return Err(format_err!("No translation found: {}-{} {:?}", sgr.dim, sgr.to_n_unsafe(), sgr));
+        return Err(no_solution!("No translation found: {}-{} {:?}", sgr.dim, sgr.to_n_unsafe(), sgr));
       }
       vec.push(translations.get(&sgr).unwrap().clone());
     }
@@ -366,9 +338,28 @@ fn solve<P>(input: P) -> Result<String>
   Ok(format!("{:?}", count))
 }
 
-fn main() {
-  match solve(PathBuf::from("input.txt")) {
-    Ok(msg) => println!("Result: {}", msg),
-    Err(err) => println!("Error: {}", err),
+pub struct Day21;
+
+impl Solution for Day21 {
+  type Input = PatternMap;
+
+  fn parse(data: &str) -> Result<PatternMap> {
+    let mut res = HashMap::new();
+    for (idx, line) in data.lines().enumerate() {
+      let (grid_from, grid_to) = Grid::from_line(line).map_err(|err| err.at(idx + 1, 1, line))?;
+      if res.contains_key(&grid_from) {
+        return Err(AocError::parse(idx + 1, 1, line, "Duplicate from Grid in input"));
+      }
+      res.insert(grid_from, grid_to);
+    }
+    Ok(res)
+  }
+
+  fn part1(translations: &PatternMap) -> Result<String> {
+    enhance(translations, 5)
+  }
+
+  fn part2(translations: &PatternMap) -> Result<String> {
+    enhance(translations, 18)
   }
 }
@@ -0,0 +1,91 @@
+use aoc_common::{Result, Solution, invalid_input};
+use aoc_vm::{Analysis, COPROCESSOR, CPU, Op, Program, Step};
+
+
+// The program written out for `vm asm coprocessor`, which turns it back into
+// the input.
+//
+//           set b 93
+//           set c b
+//           jnz a prod
+//           jnz 1 start
+// prod:     mul b 100
+//           sub b -100000
+//           set c b
+//           sub c -17000
+// start:    set f 1           ; f stays 1 while b looks prime
+//           set d 2
+// inner:    set e 2
+// tight:    set g d
+//           mul g e
+//           sub g b
+//           jnz g notfound
+//           set f 0           ; d * e == b
+// notfound: sub e -1
+//           set g e
+//           sub g b
+//           jnz g tight
+//           sub d -1
+//           set g d
+//           sub g b
+//           jnz g inner
+//           jnz f nosub
+//           sub h -1          ; count the composites
+// nosub:    set g b
+//           sub g c
+//           jnz g notdone
+//           jnz 1 halt
+// notdone:  sub b -17
+//           jnz 1 start
+// halt:
+
+pub struct Day23;
+
+impl Solution for Day23 {
+  type Input = Program;
+
+  // The loops are replaced with native code, which leaves the counts part 1
+  // needs the same and lets part 2 finish.
+  fn parse(data: &str) -> Result<Program> {
+    let mut program = COPROCESSOR.compile(data)?;
+    program.optimize();
+    Ok(program)
+  }
+
+  fn part1(program: &Program) -> Result<String> {
+    let mut cpu = CPU::new(program);
+    while cpu.step()? != Step::Halted {}
+    Ok(format!("{}", cpu.count(Op::Mul)))
+  }
+
+  // Runs the program above with debugging off, counting the composite numbers
+  // from b to c in steps of 17 into h. When the analysis works out that range
+  // without running anything, the run has to end where it says.
+  fn part2(program: &Program) -> Result<String> {
+    if program.superinstructions() == 0 {
+      return Err(invalid_input!("No loops recognized, so the program would take too long"));
+    }
+    let mut cpu = CPU::new(program);
+    cpu.set_reg("a", 1)?;
+    while cpu.step()? != Step::Halted {}
+    // Where the analysis can't tell, the run's answer stands on its own.
+    let analysis = Analysis::new(program.instrs(), &[("a".to_owned(), 1)]).ok();
+    if let Some((reg, to)) = analysis.as_ref().and_then(outer_end) {
+      let end = cpu.reg(&reg)?;
+      if end != to {
+        return Err(invalid_input!("The analysis has {} end at {}, but the run left it at {}",
+                                  reg, to, end));
+      }
+    }
+    Ok(format!("{}", cpu.reg("h")?))
+  }
+}
+
+// The outermost loop's only induction variable and where it ends.
+fn outer_end(analysis: &Analysis) -> Option<(String, i64)> {
+  let outer = analysis.loops.iter().find(|l| l.depth == 1)?;
+  match *outer.inductions.as_slice() {
+    [ref ind] => Some((ind.reg.clone(), ind.to.as_ref()?.as_const()?)),
+    _ => None,
+  }
+}
@@ -0,0 +1,131 @@
+use aoc_common::{Result, Solution, invalid_input, no_solution, to_i64};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+
+#[derive(Debug)]
+pub struct Tube {
+  sides: (i64, i64),
+}
+
+pub type TubeRef = Rc<RefCell<Tube>>;
+
+impl Tube {
+  fn new(s1: i64, s2: i64) -> TubeRef {
+    Rc::new(RefCell::new(Tube {
+      sides: (s1, s2),
+    }))
+  }
+}
+
+fn insert_tube(m: &mut HashMap<i64, Vec<TubeRef>>, n: i64, tube: &TubeRef) {
+  m.entry(n).or_default().push(Rc::clone(tube));
+}
+
+fn get_next_size(prev: &TubeRef, cur: &TubeRef) -> i64 {
+  let p = prev.borrow();
+  let c = cur.borrow();
+  if p.sides.0 == c.sides.0 {
+    c.sides.1
+  } else if p.sides.0 == c.sides.1 {
+    c.sides.0
+  } else if p.sides.1 == c.sides.0 {
+    c.sides.1
+  } else {
+    c.sides.0
+  }
+}
+
+fn is_already_used(base: &[TubeRef], potential: &TubeRef) -> bool {
+  for t in base {
+    if Rc::ptr_eq(potential, t) {
+      return true;
+    }
+  }
+  false
+}
+
+fn calc_value(v: &[TubeRef]) -> i64 {
+  v.iter()
+   .fold(0, |acc, t_ref| {
+     let t = t_ref.borrow();
+     acc + t.sides.0 + t.sides.1
+   })
+}
+
+/// Builds every bridge starting from the zero port and hands each one that
+/// can't be extended any further to `finished`.
+fn build_bridges<F>(tube_map: &HashMap<i64, Vec<TubeRef>>, mut finished: F) -> Result<()>
+    where F: FnMut(&[TubeRef]) {
+  let marker = Tube::new(0, 0);
+  let mut q = VecDeque::new();
+  let starts = tube_map.get(&0)
+                       .ok_or_else(|| no_solution!("No component has a zero port"))?;
+  for t in starts {
+    q.push_back(vec![Rc::clone(&marker), Rc::clone(t)]);
+  }
+  while let Some(base) = q.pop_front() {
+    let next = {
+      let prev = &base[base.len() - 2];
+      let cur = &base[base.len() - 1];
+      get_next_size(prev, cur)
+    };
+    let mut new_potentials = false;
+    if let Some(potentials) = tube_map.get(&next) {
+      for nx in potentials {
+        if is_already_used(&base, nx) {
+          continue;
+        }
+        let mut v = base.clone();
+        v.push(Rc::clone(nx));
+        q.push_back(v);
+        new_potentials = true;
+      }
+    }
+    if !new_potentials {
+      finished(&base);
+    }
+  }
+  Ok(())
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+  type Input = HashMap<i64, Vec<TubeRef>>;
+
+  fn parse(data: &str) -> Result<HashMap<i64, Vec<TubeRef>>> {
+    let mut hm = HashMap::new();
+    for line in data.lines() {
+      let toks =
+          line.split("/")
+              .map(to_i64)
+              .collect::<Result<Vec<i64>>>()?;
+      if toks.len() != 2 {
+        return Err(invalid_input!("Incorrect number of pipe outputs: {}", toks.len()));
+      }
+      let tube = Tube::new(toks[0], toks[1]);
+      insert_tube(&mut hm, toks[0], &tube);
+      insert_tube(&mut hm, toks[1], &tube);
+    }
+    Ok(hm)
+  }
+
+  fn part1(tube_map: &HashMap<i64, Vec<TubeRef>>) -> Result<String> {
+    let mut largest = 0;
+    build_bridges(tube_map, |bridge| {
+      largest = largest.max(calc_value(bridge));
+    })?;
+    Ok(format!("{}", largest))
+  }
+
+  fn part2(tube_map: &HashMap<i64, Vec<TubeRef>>) -> Result<String> {
+    // Longest bridge first, strongest among those to break ties.
+    let mut best = (0, 0);
+    build_bridges(tube_map, |bridge| {
+      best = best.max((bridge.len(), calc_value(bridge)));
+    })?;
+    Ok(format!("{}", best.1))
+  }
+}
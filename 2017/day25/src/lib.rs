@@ -0,0 +1,137 @@
+use aoc_common::{Result, Solution, invalid_input, no_solution, parse_lines, to_i64, to_usize};
+use std::collections::{HashMap, LinkedList};
+
+
+#[derive(Debug)]
+enum Line {
+  Blank,
+  Begin(char),
+  Steps(usize),
+  State(char),
+  Value(i64),
+  Write(i64),
+  Move(bool),
+  Next(char),
+}
+
+#[derive(Debug)]
+pub struct Rule {
+  write: i64,
+  right: bool,
+  next: char,
+}
+
+#[derive(Debug)]
+pub struct Blueprint {
+  start: char,
+  steps: usize,
+  // Keyed by the state and the value under the cursor.
+  rules: HashMap<(char, i64), Rule>,
+}
+
+fn to_state(s: &str) -> Result<char> {
+  let mut chars = s.chars();
+  match (chars.next(), chars.next()) {
+    (Some(c), None) => Ok(c),
+    _ => Err(invalid_input!("Invalid state: {}", s)),
+  }
+}
+
+fn parse_line(line: &str) -> Result<Line> {
+  let line = line.trim().trim_start_matches("- ").trim_end_matches(['.', ':']);
+  if line.is_empty() {
+    Ok(Line::Blank)
+  } else if let Some(rest) = line.strip_prefix("Begin in state ") {
+    Ok(Line::Begin(to_state(rest)?))
+  } else if let Some(rest) = line.strip_prefix("Perform a diagnostic checksum after ") {
+    Ok(Line::Steps(to_usize(rest.trim_end_matches(" steps"))?))
+  } else if let Some(rest) = line.strip_prefix("In state ") {
+    Ok(Line::State(to_state(rest)?))
+  } else if let Some(rest) = line.strip_prefix("If the current value is ") {
+    Ok(Line::Value(to_i64(rest)?))
+  } else if let Some(rest) = line.strip_prefix("Write the value ") {
+    Ok(Line::Write(to_i64(rest)?))
+  } else if let Some(rest) = line.strip_prefix("Move one slot to the ") {
+    match rest {
+      "right" => Ok(Line::Move(true)),
+      "left" => Ok(Line::Move(false)),
+      _ => Err(invalid_input!("Invalid direction: {}", rest)),
+    }
+  } else if let Some(rest) = line.strip_prefix("Continue with state ") {
+    Ok(Line::Next(to_state(rest)?))
+  } else {
+    Err(invalid_input!("Unknown blueprint line"))
+  }
+}
+
+fn do_left(left: &mut LinkedList<i64>, right: &mut LinkedList<i64>, val: i64) -> i64 {
+  right.push_front(val);
+  left.pop_back().unwrap_or(0)
+}
+
+fn do_right(left: &mut LinkedList<i64>, right: &mut LinkedList<i64>, val: i64) -> i64 {
+  left.push_back(val);
+  right.pop_front().unwrap_or(0)
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+  type Input = Blueprint;
+  const PARTS: u32 = 1;
+
+  fn parse(data: &str) -> Result<Blueprint> {
+    let lines = parse_lines(data, parse_line)?;
+    let mut start = None;
+    let mut steps = None;
+    let mut state = None;
+    let mut rules = HashMap::new();
+    let mut it = lines.into_iter().filter(|line| !matches!(line, Line::Blank));
+    while let Some(line) = it.next() {
+      match line {
+        Line::Begin(s) => start = Some(s),
+        Line::Steps(n) => steps = Some(n),
+        Line::State(s) => state = Some(s),
+        Line::Value(val) => {
+          let state = state.ok_or_else(|| invalid_input!("Rule outside of a state"))?;
+          let rule = match (it.next(), it.next(), it.next()) {
+            (Some(Line::Write(write)), Some(Line::Move(right)), Some(Line::Next(next))) => {
+              Rule { write, right, next }
+            },
+            _ => return Err(invalid_input!("Incomplete rule for state {} value {}", state, val)),
+          };
+          rules.insert((state, val), rule);
+        },
+        other => return Err(invalid_input!("Unexpected blueprint line: {:?}", other)),
+      }
+    }
+    Ok(Blueprint {
+      start: start.ok_or_else(|| invalid_input!("Missing start state"))?,
+      steps: steps.ok_or_else(|| invalid_input!("Missing checksum step count"))?,
+      rules,
+    })
+  }
+
+  fn part1(blueprint: &Blueprint) -> Result<String> {
+    let mut state = blueprint.start;
+    let mut left = LinkedList::new();
+    let mut right = LinkedList::new();
+    let mut cur = 0;
+    for _ in 0..blueprint.steps {
+      let rule = blueprint.rules.get(&(state, cur))
+          .ok_or_else(|| no_solution!("No rule for state {} value {}", state, cur))?;
+      cur = if rule.right {
+        do_right(&mut left, &mut right, rule.write)
+      } else {
+        do_left(&mut left, &mut right, rule.write)
+      };
+      state = rule.next;
+    }
+    let chksum = left.iter().sum::<i64>() + right.iter().sum::<i64>() + cur;
+    Ok(format!("{}", chksum))
+  }
+
+  fn part2(_blueprint: &Blueprint) -> Result<String> {
+    Err(no_solution!("Day 25 only has one part"))
+  }
+}
@@ -0,0 +1,35 @@
+//! Times parsing and each part separately for every day, against its own
+//! input. Filter with e.g. `cargo bench -p aoc -- 2017/day15`.
+
+use aoc::default_input;
+use aoc::registry;
+use aoc_common::read_input;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+
+fn bench_days(c: &mut Criterion) {
+  for entry in registry::SOLUTIONS {
+    let name = format!("{}/day{:02}", entry.year, entry.day);
+    let data = read_input(default_input(entry.year, entry.day))
+        .unwrap_or_else(|err| panic!("{}: {}", name, err));
+    let input = entry.parse(&data).unwrap_or_else(|err| panic!("{}: {}", name, err));
+    let mut group = c.benchmark_group(&name);
+    group.bench_function("parse", |b| b.iter(|| entry.parse(black_box(&data))));
+    for part in 1..=entry.parts {
+      group.bench_function(format!("part{}", part), |b| {
+        b.iter(|| entry.solve(black_box(&input), part))
+      });
+    }
+    group.finish();
+  }
+}
+
+// The slowest days take seconds per run, so stick to criterion's smallest
+// sample size.
+criterion_group! {
+  name = benches;
+  config = Criterion::default().sample_size(10);
+  targets = bench_days
+}
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+//! Quick wall-clock timings for `aoc bench`. The criterion benches under
+//! benches/ are the ones to trust when comparing changes.
+
+use crate::registry::{Entry, Result};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const MAX_RUNS: u32 = 10;
+const BUDGET: Duration = Duration::from_secs(1);
+
+/// How long each step took, or None if it failed, e.g. a part that isn't
+/// solved yet.
+#[derive(Debug)]
+pub struct Timings {
+  pub parse: Option<Duration>,
+  pub parts: Vec<Option<Duration>>,
+}
+
+// Mean time of `f` over up to MAX_RUNS runs, stopping early once the runs
+// have taken BUDGET so the slow days finish in reasonable time.
+fn time<T, F>(mut f: F) -> Result<Duration>
+    where F: FnMut() -> Result<T> {
+  let start = Instant::now();
+  let mut runs = 0;
+  while runs < MAX_RUNS && (runs == 0 || start.elapsed() < BUDGET) {
+    black_box(f()?);
+    runs += 1;
+  }
+  Ok(start.elapsed() / runs)
+}
+
+/// Times parsing and then each part on its own, against the same parsed input.
+pub fn time_entry(entry: &Entry, data: &str) -> Timings {
+  let parse = time(|| entry.parse(data)).ok();
+  let parts = match entry.parse(data) {
+    Ok(input) => (1..=entry.parts).map(|part| time(|| entry.solve(&input, part)).ok()).collect(),
+    Err(_) => vec![None; entry.parts as usize],
+  };
+  Timings { parse, parts }
+}
+
+impl Timings {
+  /// The steps that worked, added up.
+  pub fn total(&self) -> Duration {
+    self.parse.iter().chain(self.parts.iter().flatten()).sum()
+  }
+}
+
+pub fn format_duration(d: Duration) -> String {
+  let micros = d.as_secs_f64() * 1e6;
+  if micros < 1e3 {
+    format!("{:.1} µs", micros)
+  } else if micros < 1e6 {
+    format!("{:.1} ms", micros / 1e3)
+  } else {
+    format!("{:.2} s", micros / 1e6)
+  }
+}
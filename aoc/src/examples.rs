@@ -0,0 +1,271 @@
+//! Pulls the worked examples out of a day's problem.txt. The text is prose, so
+//! each day has an examples.toml beside it saying where its examples are:
+//!
+//! ```toml
+//! [[example]]
+//! part = 1
+//! after = "given the following spreadsheet:"  # block after the line with this
+//! lines = 3
+//! answer = "18"
+//!
+//! [[example]]
+//! part = 1
+//! pattern = '^(?P<input>\d+) produces (?P<answer>\d+)'  # one example per match
+//! ```
+//!
+//! A `pattern` on a block picks the input out of each of its lines instead,
+//! and `join` turns every match of a pattern into a single multi-line input.
+//! When the text can't be extracted at all, `input` gives it directly.
+
+use crate::day_dir;
+use crate::registry::Entry;
+use aoc_common::{AocError, Result, invalid_input, read_input};
+use regex::Regex;
+use serde::Deserialize;
+use std::io;
+use std::result;
+
+const PART_TWO: &str = "--- Part Two ---";
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Example {
+  pub part: u32,
+  pub input: String,
+  pub answer: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Annotations {
+  #[serde(default)]
+  example: Vec<Annotation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Annotation {
+  part: u32,
+  after: Option<String>,
+  lines: Option<usize>,
+  pattern: Option<String>,
+  #[serde(default)]
+  join: bool,
+  input: Option<String>,
+  answer: Option<String>,
+}
+
+impl Annotation {
+  fn answer(&self) -> Result<String> {
+    self.answer
+        .clone()
+        .ok_or_else(|| invalid_input!("Example for part {} has no answer", self.part))
+  }
+
+  fn regex(&self) -> Result<Option<Regex>> {
+    self.pattern
+        .as_ref()
+        .map(|p| Regex::new(p).map_err(|err| invalid_input!("Bad pattern {}: {}", p, err)))
+        .transpose()
+  }
+}
+
+// The part of the text that describes one part of the puzzle.
+fn section(problem: &str, part: u32) -> &str {
+  match problem.find(PART_TWO) {
+    Some(idx) if part == 1 => &problem[..idx],
+    Some(idx) => &problem[idx..],
+    None => problem,
+  }
+}
+
+// Where to look for a part's examples: its own section first, so the same
+// marker can find different blocks in each part, then the whole text for
+// parts that reuse an earlier example.
+fn search_order(problem: &str, part: u32) -> [&str; 2] {
+  [section(problem, part), problem]
+}
+
+// The `count` lines starting at the first non-blank line after the one that
+// contains `marker`.
+fn find_block<'a>(problem: &'a str, part: u32, marker: &str, count: usize)
+    -> Result<Vec<&'a str>> {
+  let lines = search_order(problem, part)
+      .into_iter()
+      .find_map(|text| {
+        let mut lines = text.lines().skip_while(|line| !line.contains(marker));
+        lines.next().map(|_| lines)
+      })
+      .ok_or_else(|| invalid_input!("No line contains '{}'", marker))?;
+  let block = lines.skip_while(|line| line.trim().is_empty()).take(count).collect::<Vec<_>>();
+  if block.len() < count {
+    return Err(invalid_input!("Only {} lines follow '{}'", block.len(), marker));
+  }
+  Ok(block)
+}
+
+fn capture_input(re: &Regex, line: &str) -> Result<String> {
+  let caps = re.captures(line)
+               .ok_or_else(|| invalid_input!("'{}' does not match {}", line, re.as_str()))?;
+  Ok(caps.name("input").map_or("", |m| m.as_str()).to_owned())
+}
+
+fn extract_one(problem: &str, annotation: &Annotation) -> Result<Vec<Example>> {
+  let part = annotation.part;
+  if let Some(ref input) = annotation.input {
+    return Ok(vec![Example { part, input: input.clone(), answer: annotation.answer()? }]);
+  }
+  let re = annotation.regex()?;
+  if let Some(ref marker) = annotation.after {
+    let count = annotation.lines
+        .ok_or_else(|| invalid_input!("Block after '{}' needs a line count", marker))?;
+    let block = find_block(problem, part, marker, count)?;
+    let lines = match re {
+      Some(ref re) => {
+        block.iter().map(|line| capture_input(re, line)).collect::<Result<Vec<_>>>()?
+      },
+      None => block.iter().map(|line| line.to_string()).collect(),
+    };
+    let input = lines.join("\n") + "\n";
+    return Ok(vec![Example { part, input, answer: annotation.answer()? }]);
+  }
+  let re = re.ok_or_else(|| invalid_input!("Example for part {} needs after, pattern or input",
+                                            part))?;
+  let mut examples = Vec::new();
+  for text in search_order(problem, part) {
+    for caps in text.lines().filter_map(|line| re.captures(line)) {
+      let input = caps.name("input").map_or("", |m| m.as_str()).to_owned();
+      let answer = match caps.name("answer") {
+        Some(m) => m.as_str().to_owned(),
+        None => annotation.answer()?,
+      };
+      examples.push(Example { part, input, answer });
+    }
+    if !examples.is_empty() {
+      break;
+    }
+  }
+  if examples.is_empty() {
+    return Err(invalid_input!("Nothing matches {}", re.as_str()));
+  }
+  if annotation.join {
+    let input = examples.iter().map(|e| e.input.as_str()).collect::<Vec<_>>().join("\n") + "\n";
+    return Ok(vec![Example { part, input, answer: annotation.answer()? }]);
+  }
+  Ok(examples)
+}
+
+/// Extracts the examples that `annotations` (the contents of examples.toml)
+/// describe from `problem`.
+pub fn extract(problem: &str, annotations: &str) -> Result<Vec<Example>> {
+  let annotations: Annotations = toml::from_str(annotations)
+      .map_err(|err| invalid_input!("Bad annotations: {}", err))?;
+  let mut examples = Vec::new();
+  for (idx, annotation) in annotations.example.iter().enumerate() {
+    let found = extract_one(problem, annotation)
+        .map_err(|err| invalid_input!("Example annotation {}: {}", idx + 1, err))?;
+    examples.extend(found);
+  }
+  Ok(examples)
+}
+
+/// The examples for one day, or none if it has no examples.toml.
+pub fn load(year: u32, day: u32) -> Result<Vec<Example>> {
+  let dir = day_dir(year, day);
+  let annotations = match read_input(dir.join("examples.toml")) {
+    Ok(data) => data,
+    Err(AocError::Io(ref err)) if err.kind() == io::ErrorKind::NotFound => {
+      return Ok(Vec::new());
+    },
+    Err(err) => return Err(err),
+  };
+  extract(&read_input(dir.join("problem.txt"))?, &annotations)
+}
+
+/// Runs one example through its day, describing the failure if it has one.
+pub fn check(entry: &Entry, example: &Example) -> result::Result<(), String> {
+  match entry.run(&example.input, &[example.part]) {
+    Ok(answers) if answers[0] == example.answer => Ok(()),
+    Ok(answers) => Err(format!("expected {:?}, got {:?}", example.answer, answers[0])),
+    Err(err) => Err(err.to_string()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const PROBLEM: &str = "\
+For example, given the following spreadsheet:
+
+5 1 9 5
+7 5 3
+The checksum would be 12.
+1122 produces a sum of 3.
+1111 produces 4.
+--- Part Two ---
+For example, given the following spreadsheet:
+5 9 2 8
+The sum would be 4.
+1212 produces 6.
+";
+
+  fn example(part: u32, input: &str, answer: &str) -> Example {
+    Example { part, input: input.to_owned(), answer: answer.to_owned() }
+  }
+
+  #[test]
+  fn test_block_per_part() {
+    let examples = extract(PROBLEM, r#"
+      [[example]]
+      part = 1
+      after = "following spreadsheet:"
+      lines = 2
+      answer = "12"
+
+      [[example]]
+      part = 2
+      after = "following spreadsheet:"
+      lines = 1
+      answer = "4"
+    "#).unwrap();
+    assert_eq!(examples, vec![example(1, "5 1 9 5\n7 5 3\n", "12"), example(2, "5 9 2 8\n", "4")]);
+  }
+
+  #[test]
+  fn test_pattern_stays_in_its_part() {
+    let examples = extract(PROBLEM, r#"
+      [[example]]
+      part = 1
+      pattern = '^(?P<input>\d+) produces (?:a sum of )?(?P<answer>\d+)'
+    "#).unwrap();
+    assert_eq!(examples, vec![example(1, "1122", "3"), example(1, "1111", "4")]);
+  }
+
+  #[test]
+  fn test_join_and_literal() {
+    let examples = extract(PROBLEM, r#"
+      [[example]]
+      part = 1
+      pattern = '^(?P<input>\d+) produces'
+      join = true
+      answer = "7"
+
+      [[example]]
+      part = 2
+      input = "3"
+      answer = "9"
+    "#).unwrap();
+    assert_eq!(examples, vec![example(1, "1122\n1111\n", "7"), example(2, "3", "9")]);
+  }
+
+  #[test]
+  fn test_missing_marker() {
+    let err = extract(PROBLEM, r#"
+      [[example]]
+      part = 1
+      after = "no such line"
+      lines = 1
+      answer = "0"
+    "#).unwrap_err();
+    assert_eq!(err.to_string(), "Example annotation 1: No line contains 'no such line'");
+  }
+}  // mod tests
@@ -0,0 +1,26 @@
+//! Every day's solution behind one registry, shared by the `aoc` binary and
+//! the regression tests.
+
+use std::path::PathBuf;
+
+pub mod bench;
+pub mod examples;
+pub mod registry;
+pub mod scaffold;
+
+/// The root of the workspace this binary was built from.
+pub fn workspace_root() -> PathBuf {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+/// The directory holding a day's crate, e.g. 2017/day18.
+pub fn day_dir(year: u32, day: u32) -> PathBuf {
+  workspace_root()
+      .join(year.to_string())
+      .join(format!("day{:02}", day))
+}
+
+/// Inputs live beside each day's crate, e.g. 2017/day18/input.txt.
+pub fn default_input(year: u32, day: u32) -> PathBuf {
+  day_dir(year, day).join("input.txt")
+}
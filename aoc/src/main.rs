@@ -0,0 +1,271 @@
+use aoc::bench::{self, format_duration};
+use aoc::examples;
+use aoc::registry::{self, Entry, Result};
+use aoc::scaffold;
+use aoc::{default_input, workspace_root};
+use aoc_common::{invalid_input, read_input};
+use serde::Serialize;
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::result;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "Usage: aoc <year> <day> [1|2|easy|hard|both] [--input <path>|-]
+                [--format text|json]
+       aoc bench [year [day]]
+       aoc examples [year [day]]
+       aoc new <year> <day>";
+
+#[derive(Debug)]
+enum Command {
+  Run(Args),
+  Bench(Option<u32>, Option<u32>),
+  Examples(Option<u32>, Option<u32>),
+  New(u32, u32),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Format {
+  Text,
+  Json,
+}
+
+#[derive(Debug)]
+struct Args {
+  year: u32,
+  day: u32,
+  part: Option<u32>,
+  input: Option<PathBuf>,
+  format: Format,
+}
+
+/// One line of `--format json` output. `elapsed_ms` includes parsing the input.
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+  year: u32,
+  day: u32,
+  part: u32,
+  answer: Option<&'a str>,
+  elapsed_ms: f64,
+  error: Option<&'a str>,
+}
+
+fn parse_year(s: &str) -> Result<u32> {
+  s.parse::<u32>().map_err(|_| invalid_input!("Invalid year: {}", s))
+}
+
+fn parse_day(s: &str) -> Result<u32> {
+  s.parse::<u32>().map_err(|_| invalid_input!("Invalid day: {}", s))
+}
+
+// Parts can also go by their old names: easy and hard, or both for all of them.
+fn parse_part(s: &str) -> Result<Option<u32>> {
+  match s {
+    "easy" => Ok(Some(1)),
+    "hard" => Ok(Some(2)),
+    "both" => Ok(None),
+    _ => s.parse::<u32>().map(Some).map_err(|_| invalid_input!("Invalid part: {}", s)),
+  }
+}
+
+fn parse_args<I>(args: I) -> Result<Command>
+    where I: IntoIterator<Item = String> {
+  let mut positional = Vec::new();
+  let mut input = None;
+  let mut format = None;
+  let mut it = args.into_iter();
+  while let Some(arg) = it.next() {
+    match arg.as_str() {
+      "--input" | "-i" => {
+        let path = it.next().ok_or_else(|| invalid_input!("Missing value for {}", arg))?;
+        input = Some(PathBuf::from(path));
+      },
+      "--format" | "-f" => {
+        let name = it.next().ok_or_else(|| invalid_input!("Missing value for {}", arg))?;
+        format = match name.as_str() {
+          "text" => Some(Format::Text),
+          "json" => Some(Format::Json),
+          _ => return Err(invalid_input!("Unknown format: {}", name)),
+        };
+      },
+      "--help" | "-h" => return Err(invalid_input!("{}", USAGE)),
+      _ => positional.push(arg),
+    }
+  }
+  let command = positional.first().map(String::as_str);
+  if command == Some("bench") || command == Some("examples") {
+    if positional.len() > 3 || input.is_some() || format.is_some() {
+      return Err(invalid_input!("{}", USAGE));
+    }
+    let year = positional.get(1).map(|y| parse_year(y)).transpose()?;
+    let day = positional.get(2).map(|d| parse_day(d)).transpose()?;
+    return Ok(if command == Some("bench") {
+      Command::Bench(year, day)
+    } else {
+      Command::Examples(year, day)
+    });
+  }
+  if command == Some("new") {
+    if positional.len() != 3 || input.is_some() || format.is_some() {
+      return Err(invalid_input!("{}", USAGE));
+    }
+    return Ok(Command::New(parse_year(&positional[1])?, parse_day(&positional[2])?));
+  }
+  if positional.len() < 2 || positional.len() > 3 {
+    return Err(invalid_input!("{}", USAGE));
+  }
+  let year = parse_year(&positional[0])?;
+  let day = parse_day(&positional[1])?;
+  let part = match positional.get(2) {
+    Some(p) => parse_part(p)?,
+    None => None,
+  };
+  Ok(Command::Run(Args {
+    year,
+    day,
+    part,
+    input,
+    format: format.unwrap_or(Format::Text),
+  }))
+}
+
+fn report(format: Format, entry: &Entry, part: u32, answer: &result::Result<String, String>,
+          elapsed: Duration) -> Result<()> {
+  match format {
+    Format::Text => match answer {
+      Ok(answer) => {
+        // Some answers are drawn as text art and read better on their own lines.
+        let sep = if answer.contains('\n') { "\n" } else { " " };
+        println!("{} day {:02} part {}:{}{}", entry.year, entry.day, part, sep, answer);
+      },
+      Err(err) => eprintln!("Error: {} day {:02} part {}: {}", entry.year, entry.day, part, err),
+    },
+    Format::Json => {
+      let record = Record {
+        year: entry.year,
+        day: entry.day,
+        part,
+        answer: answer.as_deref().ok(),
+        elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+        error: answer.as_ref().err().map(String::as_str),
+      };
+      println!("{}", serde_json::to_string(&record).map_err(io::Error::from)?);
+    },
+  }
+  Ok(())
+}
+
+/// Reports every requested part, even when an earlier one fails. Returns
+/// whether they all produced an answer.
+fn run(args: Args) -> Result<bool> {
+  let entry = registry::find(args.year, args.day)
+      .ok_or_else(|| invalid_input!("No solution for {} day {}", args.year, args.day))?;
+  let parts = match args.part {
+    Some(part) if part < 1 || part > entry.parts => {
+      return Err(invalid_input!("No part {} for {} day {}", part, args.year, args.day));
+    },
+    Some(part) => vec![part],
+    None => (1..=entry.parts).collect(),
+  };
+  let path = args.input.clone().unwrap_or_else(|| default_input(args.year, args.day));
+  let start = Instant::now();
+  let input = read_input(path).and_then(|data| entry.parse(&data));
+  let parse_time = start.elapsed();
+  let mut all_ok = true;
+  for part in parts {
+    let start = Instant::now();
+    let answer = match input {
+      Ok(ref input) => entry.solve(input, part).map_err(|err| err.to_string()),
+      Err(ref err) => Err(err.to_string()),
+    };
+    report(args.format, entry, part, &answer, parse_time + start.elapsed())?;
+    all_ok &= answer.is_ok();
+  }
+  Ok(all_ok)
+}
+
+// Every registered day matching the filters, in order.
+fn select(year: Option<u32>, day: Option<u32>) -> Result<Vec<&'static Entry>> {
+  let entries = registry::SOLUTIONS
+      .iter()
+      .filter(|e| year.is_none_or(|y| e.year == y) && day.is_none_or(|d| e.day == d))
+      .collect::<Vec<_>>();
+  if entries.is_empty() {
+    return Err(invalid_input!("No matching solutions"));
+  }
+  Ok(entries)
+}
+
+// Times every matching day against its own input and prints one row per day.
+fn bench(year: Option<u32>, day: Option<u32>) -> Result<()> {
+  let entries = select(year, day)?;
+  println!("{:<4} {:>3} {:>10} {:>10} {:>10} {:>10}",
+           "Year", "Day", "Parse", "Part 1", "Part 2", "Total");
+  let mut total = Duration::ZERO;
+  for entry in entries {
+    let data = read_input(default_input(entry.year, entry.day))?;
+    let timings = bench::time_entry(entry, &data);
+    let day_total = timings.total();
+    // Failed steps, such as parts not solved yet, show as "-".
+    let show = |d: Option<Duration>| d.map_or("-".to_owned(), format_duration);
+    let part = |idx: usize| show(timings.parts.get(idx).copied().flatten());
+    println!("{:<4} {:>3} {:>10} {:>10} {:>10} {:>10}",
+             entry.year, entry.day, show(timings.parse), part(0), part(1),
+             format_duration(day_total));
+    total += day_total;
+  }
+  println!("{:<4} {:>3} {:>10} {:>10} {:>10} {:>10}", "", "", "", "", "", format_duration(total));
+  Ok(())
+}
+
+// Runs the examples extracted from each matching day's problem.txt. Returns
+// whether they all passed.
+fn run_examples(year: Option<u32>, day: Option<u32>) -> Result<bool> {
+  let mut all_ok = true;
+  for entry in select(year, day)? {
+    for (idx, example) in examples::load(entry.year, entry.day)?.iter().enumerate() {
+      let name = format!("{} day {:02} example {} part {}", entry.year, entry.day, idx + 1,
+                         example.part);
+      match examples::check(entry, example) {
+        Ok(()) => println!("{}: ok", name),
+        Err(err) => {
+          println!("{}: {}", name, err);
+          all_ok = false;
+        },
+      }
+    }
+  }
+  Ok(all_ok)
+}
+
+// Generates and registers a new day, listing the files it wrote.
+fn new_day(year: u32, day: u32) -> Result<()> {
+  let root = workspace_root();
+  for path in scaffold::new_day(&root, year, day)? {
+    println!("Wrote {}", path.strip_prefix(&root).unwrap_or(&path).display());
+  }
+  println!("Fill in input.txt and problem.txt, solve it in src/lib.rs, then record the \
+            answers in answers.toml.");
+  Ok(())
+}
+
+fn main() {
+  let res = parse_args(env::args().skip(1)).and_then(|cmd| {
+    match cmd {
+      Command::Run(args) => run(args),
+      Command::Bench(year, day) => bench(year, day).map(|_| true),
+      Command::Examples(year, day) => run_examples(year, day),
+      Command::New(year, day) => new_day(year, day).map(|_| true),
+    }
+  });
+  match res {
+    Ok(true) => {},
+    Ok(false) => process::exit(1),
+    Err(err) => {
+      eprintln!("Error: {}", err);
+      process::exit(1);
+    },
+  }
+}
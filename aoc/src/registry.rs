@@ -0,0 +1,94 @@
+use aoc_common::{Solution, invalid_input};
+use std::any::Any;
+
+pub use aoc_common::Result;
+
+/// A day's parsed input, boxed so every day fits in the same table.
+pub type Input = Box<dyn Any>;
+
+#[derive(Debug)]
+pub struct Entry {
+  pub year: u32,
+  pub day: u32,
+  pub parts: u32,
+  parse: fn(&str) -> Result<Input>,
+  solve: fn(&Input, u32) -> Result<String>,
+}
+
+impl Entry {
+  pub fn parse(&self, data: &str) -> Result<Input> {
+    (self.parse)(data)
+  }
+
+  /// Runs one part against input returned by `parse` on this same entry.
+  pub fn solve(&self, input: &Input, part: u32) -> Result<String> {
+    (self.solve)(input, part)
+  }
+
+  /// Parses the input once and runs each requested part against it.
+  pub fn run(&self, data: &str, parts: &[u32]) -> Result<Vec<String>> {
+    let input = self.parse(data)?;
+    parts.iter()
+         .map(|&part| self.solve(&input, part))
+         .collect()
+  }
+}
+
+fn parse<S>(data: &str) -> Result<Input>
+    where S: Solution, S::Input: 'static {
+  Ok(Box::new(S::parse(data)?))
+}
+
+fn solve<S>(input: &Input, part: u32) -> Result<String>
+    where S: Solution, S::Input: 'static {
+  let input = input.downcast_ref::<S::Input>()
+                   .ok_or_else(|| invalid_input!("Input was parsed for another day"))?;
+  if part == 1 { S::part1(input) } else { S::part2(input) }
+}
+
+macro_rules! solution {
+  ($year:expr, $day:expr, $solution:ty) => {
+    Entry {
+      year: $year,
+      day: $day,
+      parts: <$solution as Solution>::PARTS,
+      parse: parse::<$solution>,
+      solve: solve::<$solution>,
+    }
+  };
+}
+
+pub static SOLUTIONS: &[Entry] = &[
+  solution!(2016, 6, aoc2016_day06::Day06),
+  solution!(2016, 7, aoc2016_day07::Day07),
+  solution!(2016, 8, aoc2016_day08::Day08),
+  solution!(2017, 1, aoc2017_day01::Day01),
+  solution!(2017, 2, aoc2017_day02::Day02),
+  solution!(2017, 3, aoc2017_day03::Day03),
+  solution!(2017, 4, aoc2017_day04::Day04),
+  solution!(2017, 5, aoc2017_day05::Day05),
+  solution!(2017, 6, aoc2017_day06::Day06),
+  solution!(2017, 7, aoc2017_day07::Day07),
+  solution!(2017, 8, aoc2017_day08::Day08),
+  solution!(2017, 9, aoc2017_day09::Day09),
+  solution!(2017, 10, aoc2017_day10::Day10),
+  solution!(2017, 11, aoc2017_day11::Day11),
+  solution!(2017, 12, aoc2017_day12::Day12),
+  solution!(2017, 13, aoc2017_day13::Day13),
+  solution!(2017, 14, aoc2017_day14::Day14),
+  solution!(2017, 15, aoc2017_day15::Day15),
+  solution!(2017, 16, aoc2017_day16::Day16),
+  solution!(2017, 17, aoc2017_day17::Day17),
+  solution!(2017, 18, aoc2017_day18::Day18),
+  solution!(2017, 19, aoc2017_day19::Day19),
+  solution!(2017, 20, aoc2017_day20::Day20),
+  solution!(2017, 21, aoc2017_day21::Day21),
+  solution!(2017, 22, aoc2017_day22::Day22),
+  solution!(2017, 23, aoc2017_day23::Day23),
+  solution!(2017, 24, aoc2017_day24::Day24),
+  solution!(2017, 25, aoc2017_day25::Day25),
+];
+
+pub fn find(year: u32, day: u32) -> Option<&'static Entry> {
+  SOLUTIONS.iter().find(|e| e.year == year && e.day == day)
+}
@@ -0,0 +1,229 @@
+//! Generates the crate for a new day and registers it everywhere the
+//! workspace lists its days: the workspace members, the runner's dependencies,
+//! the registry and answers.toml. The criterion benches and the example and
+//! answer tests all walk the registry, so they pick the day up from there;
+//! the day also gets a bench of its own to run while solving it.
+
+use aoc_common::{Result, invalid_input, read_input};
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = r#"[package]
+name = "aoc{year}-day{day}"
+version = "0.{n}.0"
+edition = "2021"
+
+[dependencies]
+"aoc-common" = { path = "../../common" }
+
+[dev-dependencies]
+criterion = "0.5"
+
+[[bench]]
+name = "day{day}"
+harness = false
+"#;
+
+const LIB_RS: &str = r#"use aoc_common::{Result, Solution, no_solution};
+
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+  type Input = Vec<String>;
+
+  fn parse(data: &str) -> Result<Vec<String>> {
+    Ok(data.lines().map(|line| line.to_owned()).collect())
+  }
+
+  fn part1(_lines: &Vec<String>) -> Result<String> {
+    Err(no_solution!("Part 1 is not solved yet"))
+  }
+
+  fn part2(_lines: &Vec<String>) -> Result<String> {
+    Err(no_solution!("Part 2 is not solved yet"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    assert_eq!(Day{day}::parse("a\nb\n").unwrap(), vec!["a", "b"]);
+  }
+}  // mod tests
+"#;
+
+const BENCH_RS: &str = r#"//! Times this day on its own, with `cargo bench -p aoc{year}-day{day}`.
+//! `aoc bench` and the runner's benches time it alongside every other day.
+
+use aoc_common::{Solution, read_input};
+use aoc{year}_day{day}::Day{day};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+
+fn bench_day(c: &mut Criterion) {
+  let data = read_input(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")).unwrap();
+  c.bench_function("parse", |b| b.iter(|| Day{day}::parse(black_box(&data))));
+  let input = Day{day}::parse(&data).unwrap();
+  c.bench_function("part1", |b| b.iter(|| Day{day}::part1(black_box(&input))));
+  c.bench_function("part2", |b| b.iter(|| Day{day}::part2(black_box(&input))));
+}
+
+criterion_group! {
+  name = benches;
+  config = Criterion::default().sample_size(10);
+  targets = bench_day
+}
+criterion_main!(benches);
+"#;
+
+const EXAMPLES_TOML: &str = r#"# Where the worked examples are in problem.txt, checked by
+# `aoc examples` and `cargo test -p aoc`. See aoc/src/examples.rs for the
+# other ways to find them.
+
+# [[example]]
+# part = 1
+# after = "For example"
+# lines = 1
+# answer = ""
+"#;
+
+// Fills in a template for one day: `{day}` is zero-padded, `{n}` isn't.
+fn render(template: &str, year: u32, day: u32) -> String {
+  template.replace("{year}", &year.to_string())
+          .replace("{day}", &format!("{:02}", day))
+          .replace("{n}", &day.to_string())
+}
+
+// Inserts `new_line` among the lines that name a day, keeping them ordered by
+// year and day. Those lines all mention the year followed by the day, e.g.
+// `"2017/day01",` or `solution!(2017, 1, aoc2017_day01::Day01),`.
+fn insert_sorted(text: &str, new_line: &str, year: u32, day: u32) -> Result<String> {
+  let re = Regex::new(r"(\d{4})\D+(\d{1,2})\b").unwrap();
+  let mut lines = text.lines().collect::<Vec<_>>();
+  let days = lines.iter()
+                  .enumerate()
+                  .filter_map(|(idx, line)| {
+                    let caps = re.captures(line)?;
+                    Some((idx, (caps[1].parse::<u32>().ok()?, caps[2].parse::<u32>().ok()?)))
+                  })
+                  .collect::<Vec<_>>();
+  if days.iter().any(|&(_, key)| key == (year, day)) {
+    return Err(invalid_input!("{} day {} is already registered", year, day));
+  }
+  let idx = match days.iter().find(|&&(_, key)| key > (year, day)) {
+    Some(&(idx, _)) => idx,
+    None => days.last().map(|&(idx, _)| idx + 1)
+                .ok_or_else(|| invalid_input!("No days listed to register beside"))?,
+  };
+  lines.insert(idx, new_line);
+  Ok(lines.join("\n") + "\n")
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+  fs::write(path, contents)
+      .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+  Ok(())
+}
+
+/// Creates `<year>/dayNN` under the workspace at `root` and registers it.
+/// Returns every file written. An input.txt or problem.txt that is already
+/// there is kept.
+pub fn new_day(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>> {
+  if !(1..=25).contains(&day) {
+    return Err(invalid_input!("Invalid day: {}", day));
+  }
+  let member = format!("{}/day{:02}", year, day);
+  let dir = root.join(&member);
+  if dir.join("Cargo.toml").exists() {
+    return Err(invalid_input!("{} already exists", member));
+  }
+
+  // Work out every registration before writing anything, so a day that is
+  // already half registered leaves the tree untouched.
+  let mut edits = Vec::new();
+  let registrations = [
+    ("Cargo.toml", format!("  \"{}\",", member)),
+    ("aoc/Cargo.toml", format!("\"aoc{}-day{:02}\" = {{ path = \"../{}\" }}", year, day, member)),
+    ("aoc/src/registry.rs",
+     format!("  solution!({}, {}, aoc{}_day{:02}::Day{:02}),", year, day, year, day, day)),
+  ];
+  for (file, line) in registrations {
+    let path = root.join(file);
+    let text = insert_sorted(&read_input(&path)?, &line, year, day)
+        .map_err(|err| invalid_input!("{}: {}", path.display(), err))?;
+    edits.push((path, text));
+  }
+  let answers = root.join("answers.toml");
+  edits.push((answers.clone(),
+              format!("{}\n[[day]]\nyear = {}\nday = {}\n", read_input(&answers)?, year, day)));
+
+  fs::create_dir_all(dir.join("src"))?;
+  fs::create_dir_all(dir.join("benches"))?;
+  let mut files = vec![
+    (dir.join("Cargo.toml"), render(CARGO_TOML, year, day)),
+    (dir.join("src").join("lib.rs"), render(LIB_RS, year, day)),
+    (dir.join("benches").join(format!("day{:02}.rs", day)), render(BENCH_RS, year, day)),
+    (dir.join("examples.toml"), EXAMPLES_TOML.to_owned()),
+  ];
+  for name in ["input.txt", "problem.txt"] {
+    if !dir.join(name).exists() {
+      files.push((dir.join(name), String::new()));
+    }
+  }
+  files.extend(edits);
+  for (path, contents) in &files {
+    write(path, contents)?;
+  }
+  Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const REGISTRY: &str = "\
+pub static SOLUTIONS: &[Entry] = &[
+  solution!(2017, 1, aoc2017_day01::Day01),
+  solution!(2017, 10, aoc2017_day10::Day10),
+];
+";
+
+  #[test]
+  fn test_insert_sorted() {
+    let text = insert_sorted(REGISTRY, "  solution!(2017, 2, aoc2017_day02::Day02),", 2017, 2)
+        .unwrap();
+    assert_eq!(text, "\
+pub static SOLUTIONS: &[Entry] = &[
+  solution!(2017, 1, aoc2017_day01::Day01),
+  solution!(2017, 2, aoc2017_day02::Day02),
+  solution!(2017, 10, aoc2017_day10::Day10),
+];
+");
+    let text = insert_sorted(REGISTRY, "  solution!(2018, 1, aoc2018_day01::Day01),", 2018, 1)
+        .unwrap();
+    assert!(text.ends_with("  solution!(2018, 1, aoc2018_day01::Day01),\n];\n"));
+  }
+
+  #[test]
+  fn test_insert_sorted_already_registered() {
+    let err = insert_sorted(REGISTRY, "  solution!(2017, 10, aoc2017_day10::Day10),", 2017, 10)
+        .unwrap_err();
+    assert_eq!(err.to_string(), "2017 day 10 is already registered");
+  }
+
+  #[test]
+  fn test_render() {
+    assert!(render(LIB_RS, 2018, 3).contains("pub struct Day03;\n\nimpl Solution for Day03 {"));
+    assert!(render(LIB_RS, 2018, 3).contains("Day03::parse("));
+    assert!(render(BENCH_RS, 2018, 3).contains("use aoc2018_day03::Day03;"));
+    let cargo_toml = render(CARGO_TOML, 2018, 3);
+    assert!(cargo_toml.contains("name = \"aoc2018-day03\"\nversion = \"0.3.0\""));
+  }
+}  // mod tests
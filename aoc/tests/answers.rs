@@ -0,0 +1,117 @@
+//! Runs every day against the answers recorded in answers.toml at the root of
+//! the workspace.
+
+use aoc::default_input;
+use aoc::registry::{self, Entry};
+use aoc_common::read_input;
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+
+
+#[derive(Debug, Deserialize)]
+struct Answers {
+  day: Vec<Day>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Day {
+  year: u32,
+  day: u32,
+  part1: Option<String>,
+  part2: Option<String>,
+}
+
+impl Day {
+  // `aoc new` registers a day before it has any answers.
+  fn is_solved(&self) -> bool {
+    self.part1.is_some() || self.part2.is_some()
+  }
+}
+
+fn load_answers() -> Answers {
+  let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("answers.toml");
+  let data = fs::read_to_string(&path)
+      .unwrap_or_else(|err| panic!("Unable to read {}: {}", path.display(), err));
+  toml::from_str(&data).unwrap_or_else(|err| panic!("Bad {}: {}", path.display(), err))
+}
+
+fn find_entry(day: &Day) -> &'static Entry {
+  registry::find(day.year, day.day)
+      .unwrap_or_else(|| panic!("No solution for {} day {}", day.year, day.day))
+}
+
+// Runs the parts that have an expected answer and describes each mismatch.
+fn check(entry: &Entry, name: &str, data: &str, part1: &Option<String>, part2: &Option<String>)
+    -> Vec<String> {
+  let expected = [(1, part1), (2, part2)].into_iter()
+      .filter_map(|(part, answer)| answer.as_ref().map(|answer| (part, answer)))
+      .collect::<Vec<_>>();
+  let parts = expected.iter().map(|&(part, _)| part).collect::<Vec<_>>();
+  match entry.run(data, &parts) {
+    Ok(answers) => {
+      expected.iter()
+              .zip(answers)
+              // Multi-line answers are recorded with a trailing newline.
+              .filter(|((_, want), got)| want.trim_end_matches('\n') != got)
+              .map(|((part, want), got)| {
+                format!("{} part {}: expected {:?}, got {:?}", name, part, want, got)
+              })
+              .collect()
+    },
+    Err(err) => vec![format!("{}: {}", name, err)],
+  }
+}
+
+// Checks every recorded day on its own thread, collecting all the failures so
+// one broken day doesn't hide another.
+fn check_all<F>(answers: &Answers, f: F) -> Vec<String>
+    where F: Fn(&Day) -> Vec<String> + Sync {
+  thread::scope(|s| {
+    let handles = answers.day
+        .iter()
+        .map(|day| (day, s.spawn(|| f(day))))
+        .collect::<Vec<_>>();
+    handles.into_iter()
+           .flat_map(|(day, handle)| {
+             handle.join().unwrap_or_else(|_| {
+               vec![format!("{} day {:02}: panicked", day.year, day.day)]
+             })
+           })
+           .collect()
+  })
+}
+
+#[test]
+fn test_every_solution_has_answers() {
+  let answers = load_answers();
+  for entry in registry::SOLUTIONS {
+    let day = answers.day.iter().find(|day| day.year == entry.year && day.day == entry.day);
+    match day {
+      Some(day) if !day.is_solved() => {},
+      Some(day) => {
+        assert!(day.part1.is_some(), "No part 1 answer for {} day {}", entry.year, entry.day);
+        assert!(entry.parts < 2 || day.part2.is_some(),
+                "No part 2 answer for {} day {}", entry.year, entry.day);
+      },
+      None => panic!("No answers for {} day {}", entry.year, entry.day),
+    }
+  }
+}
+
+#[test]
+fn test_inputs() {
+  let answers = load_answers();
+  let failures = check_all(&answers, |day| {
+    if !day.is_solved() {
+      return Vec::new();
+    }
+    let name = format!("{} day {:02}", day.year, day.day);
+    match read_input(default_input(day.year, day.day)) {
+      Ok(data) => check(find_entry(day), &name, &data, &day.part1, &day.part2),
+      Err(err) => vec![format!("{}: {}", name, err)],
+    }
+  });
+  assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
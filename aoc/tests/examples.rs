@@ -0,0 +1,42 @@
+//! Runs the worked examples that each day's examples.toml locates in its
+//! problem.txt.
+
+use aoc::examples;
+use aoc::registry;
+use std::thread;
+
+
+#[test]
+fn test_examples() {
+  let failures = thread::scope(|s| {
+    let handles = registry::SOLUTIONS
+        .iter()
+        .map(|entry| {
+          (entry, s.spawn(move || {
+            let examples = match examples::load(entry.year, entry.day) {
+              Ok(examples) => examples,
+              Err(err) => return vec![err.to_string()],
+            };
+            examples.iter()
+                    .enumerate()
+                    .filter_map(|(idx, example)| {
+                      examples::check(entry, example).err().map(|err| {
+                        format!("example {} part {}: {}", idx + 1, example.part, err)
+                      })
+                    })
+                    .collect::<Vec<_>>()
+          }))
+        })
+        .collect::<Vec<_>>();
+    handles.into_iter()
+           .flat_map(|(entry, handle)| {
+             let name = format!("{} day {:02}", entry.year, entry.day);
+             handle.join()
+                   .unwrap_or_else(|_| vec!["panicked".to_owned()])
+                   .into_iter()
+                   .map(move |err| format!("{} {}", name, err))
+           })
+           .collect::<Vec<_>>()
+  });
+  assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,71 @@
+//! Generates a day with `aoc new` in a copy of the workspace, then checks the
+//! copy still builds and the new day runs, benches and passes its own tests.
+
+use aoc::registry;
+use aoc::scaffold;
+use aoc::workspace_root;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+
+// Everything but build output and git's own files.
+fn copy_tree(from: &Path, to: &Path) {
+  fs::create_dir_all(to).unwrap();
+  for entry in fs::read_dir(from).unwrap() {
+    let entry = entry.unwrap();
+    let name = entry.file_name();
+    if name == "target" || name == ".git" {
+      continue;
+    }
+    if entry.file_type().unwrap().is_dir() {
+      copy_tree(&entry.path(), &to.join(&name));
+    } else {
+      fs::copy(entry.path(), to.join(&name)).unwrap();
+    }
+  }
+}
+
+fn check(what: &str, out: &Output) {
+  assert!(out.status.success(), "{} failed:\n{}{}", what, String::from_utf8_lossy(&out.stdout),
+          String::from_utf8_lossy(&out.stderr));
+}
+
+#[test]
+fn test_new_day() {
+  // The build output is kept between runs, so only the first one is slow.
+  let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("scaffold");
+  let (root, target) = (dir.join("workspace"), dir.join("target"));
+  if root.exists() {
+    fs::remove_dir_all(&root).unwrap();
+  }
+  copy_tree(&workspace_root(), &root);
+  let year = registry::SOLUTIONS.iter().map(|e| e.year).max().unwrap() + 1;
+  scaffold::new_day(&root, year, 1).unwrap();
+
+  let cargo = |args: &[&str]| {
+    Command::new(env!("CARGO")).args(args)
+                               .arg("--offline")
+                               .current_dir(&root)
+                               .env("CARGO_TARGET_DIR", &target)
+                               .output()
+                               .unwrap()
+  };
+  check("cargo build", &cargo(&["build", "-p", "aoc"]));
+  // The generated tests and bench, the bench run once as a test.
+  let package = format!("aoc{}-day01", year);
+  check("cargo test", &cargo(&["test", "-p", &package, "--all-targets"]));
+
+  let aoc = |args: &[&str]| {
+    Command::new(target.join("debug").join("aoc")).args(args).output().unwrap()
+  };
+  let year = year.to_string();
+  let out = aoc(&[&year, "1"]);
+  assert!(!out.status.success());
+  assert!(String::from_utf8_lossy(&out.stderr).contains("Part 1 is not solved yet"));
+  let out = aoc(&["bench", &year, "1"]);
+  check("aoc bench", &out);
+  let row = String::from_utf8_lossy(&out.stdout).lines().nth(1).unwrap().to_owned();
+  assert!(row.starts_with(&format!("{}   1", year)), "{}", row);
+  assert_eq!(row.split_whitespace().filter(|&col| col == "-").count(), 2, "{}", row);
+}
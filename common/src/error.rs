@@ -0,0 +1,118 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+
+/// Everything a solution can fail with.
+#[derive(Debug)]
+pub enum AocError {
+  /// Malformed input pinned to a 1-based line and column.
+  Parse {
+    line: usize,
+    col: usize,
+    snippet: String,
+    msg: String,
+  },
+  Io(io::Error),
+  /// Input that parsed but makes no sense for the puzzle.
+  InvalidInput(String),
+  /// The search finished without finding an answer.
+  NoSolution(String),
+}
+
+impl AocError {
+  pub fn parse(line: usize, col: usize, snippet: &str, msg: &str) -> AocError {
+    AocError::Parse {
+      line,
+      col,
+      snippet: snippet.to_owned(),
+      msg: msg.to_owned(),
+    }
+  }
+
+  /// Pins an error to a location in the input. Errors that already carry a
+  /// location keep it.
+  pub fn at(self, line: usize, col: usize, snippet: &str) -> AocError {
+    match self {
+      AocError::Parse { .. } => self,
+      err => AocError::parse(line, col, snippet, &err.to_string()),
+    }
+  }
+}
+
+impl fmt::Display for AocError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      AocError::Parse { line, col, ref snippet, ref msg } => {
+        write!(f, "line {}, col {}: {} (in '{}')", line, col, msg, snippet)
+      },
+      AocError::Io(ref err) => write!(f, "I/O error: {}", err),
+      AocError::InvalidInput(ref msg) => write!(f, "{}", msg),
+      AocError::NoSolution(ref msg) => write!(f, "No solution: {}", msg),
+    }
+  }
+}
+
+impl Error for AocError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match *self {
+      AocError::Io(ref err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+impl From<io::Error> for AocError {
+  fn from(err: io::Error) -> AocError {
+    AocError::Io(err)
+  }
+}
+
+// Formatting only fails when the underlying writer does.
+impl From<fmt::Error> for AocError {
+  fn from(err: fmt::Error) -> AocError {
+    AocError::Io(io::Error::other(err))
+  }
+}
+
+impl From<ParseIntError> for AocError {
+  fn from(err: ParseIntError) -> AocError {
+    AocError::InvalidInput(err.to_string())
+  }
+}
+
+/// Builds an `AocError::InvalidInput` from a format string.
+#[macro_export]
+macro_rules! invalid_input {
+  ($($arg:tt)*) => {
+    $crate::AocError::InvalidInput(format!($($arg)*))
+  };
+}
+
+/// Builds an `AocError::NoSolution` from a format string.
+#[macro_export]
+macro_rules! no_solution {
+  ($($arg:tt)*) => {
+    $crate::AocError::NoSolution(format!($($arg)*))
+  };
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_at_pins_location() {
+    let err = invalid_input!("Unknown Move: {}", "q1").at(1, 17, "q1");
+    assert_eq!(err.to_string(), "line 1, col 17: Unknown Move: q1 (in 'q1')");
+  }
+
+  #[test]
+  fn test_at_keeps_existing_location() {
+    let err = AocError::parse(3, 5, "x/y", "bad").at(1, 1, "outer");
+    match err {
+      AocError::Parse { line, col, .. } => assert_eq!((line, col), (3, 5)),
+      err => panic!("Expected a parse error, got: {}", err),
+    }
+  }
+}  // mod tests
@@ -0,0 +1,45 @@
+use crate::error::AocError;
+use crate::Result;
+
+/// Loads a rectangular-ish character grid, one row per line. Rows keep their
+/// original length so ragged input is preserved.
+pub fn char_grid(data: &str) -> Vec<Vec<char>> {
+  data.lines().map(|line| line.chars().collect()).collect()
+}
+
+/// Loads a grid of single digit cells.
+pub fn digit_grid(data: &str, radix: u32) -> Result<Vec<Vec<u32>>> {
+  let mut res = Vec::new();
+  for (line_idx, line) in data.lines().enumerate() {
+    let mut row = Vec::with_capacity(line.len());
+    for (col_idx, c) in line.chars().enumerate() {
+      let n = c.to_digit(radix).ok_or_else(|| {
+        AocError::parse(line_idx + 1, col_idx + 1, line,
+                        &format!("Invalid digit: {}", c.escape_debug()))
+      })?;
+      row.push(n);
+    }
+    res.push(row);
+  }
+  Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_char_grid() {
+    let grid = char_grid("..#\n#.\n");
+    assert_eq!(grid, vec![vec!['.', '.', '#'], vec!['#', '.']]);
+  }
+
+  #[test]
+  fn test_digit_grid() {
+    assert_eq!(digit_grid("12\n34", 10).unwrap(), vec![vec![1, 2], vec![3, 4]]);
+    match digit_grid("12\n3?", 10).unwrap_err() {
+      AocError::Parse { line, col, .. } => assert_eq!((line, col), (2, 2)),
+      err => panic!("Expected a parse error, got: {}", err),
+    }
+  }
+}  // mod tests
@@ -0,0 +1,35 @@
+//! The `Solution` trait plus the input loading and parsing helpers shared by
+//! every day.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::result;
+
+mod error;
+mod grid;
+mod parse;
+mod solution;
+
+pub use error::AocError;
+pub use grid::{char_grid, digit_grid};
+pub use parse::{column_of, comma_separated, digits, parse_lines, parse_num, tab_separated,
+                to_digit, to_i64, to_usize, whitespace_separated};
+pub use solution::Solution;
+
+pub type Result<T> = result::Result<T, AocError>;
+
+/// Reads the whole puzzle input into memory. A path of `-` reads stdin.
+pub fn read_input<P>(path: P) -> Result<String>
+    where P: AsRef<Path> {
+  let mut data = String::new();
+  if path.as_ref() == Path::new("-") {
+    io::stdin().read_to_string(&mut data)?;
+  } else {
+    let path = path.as_ref();
+    let mut f = File::open(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+    f.read_to_string(&mut data)?;
+  }
+  Ok(data)
+}
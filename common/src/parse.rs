@@ -0,0 +1,189 @@
+use std::any;
+use std::str::FromStr;
+
+use crate::error::AocError;
+use crate::invalid_input;
+use crate::Result;
+
+/// Parses a number of any `FromStr` type, naming the type on failure.
+pub fn parse_num<T>(s: &str) -> Result<T>
+    where T: FromStr {
+  s.parse::<T>()
+   .map_err(|_| invalid_input!("Unable to parse {} from '{}'", any::type_name::<T>(), s))
+}
+
+pub fn to_i64(s: &str) -> Result<i64> {
+  parse_num(s)
+}
+
+pub fn to_usize(s: &str) -> Result<usize> {
+  parse_num(s)
+}
+
+pub fn to_digit(c: char, radix: u32) -> Result<u32> {
+  c.to_digit(radix)
+   .ok_or_else(|| invalid_input!("Invalid digit: {}", c.escape_debug()))
+}
+
+/// Parses every non-whitespace character of `data` as a single digit.
+pub fn digits(data: &str, radix: u32) -> Result<Vec<u32>> {
+  let mut res = Vec::new();
+  for (line_idx, line) in data.lines().enumerate() {
+    for (col_idx, c) in line.chars().enumerate() {
+      if c.is_whitespace() {
+        continue;
+      }
+      let n = c.to_digit(radix).ok_or_else(|| {
+        AocError::parse(line_idx + 1, col_idx + 1, line,
+                        &format!("Invalid digit: {}", c.escape_debug()))
+      })?;
+      res.push(n);
+    }
+  }
+  Ok(res)
+}
+
+/// Runs `f` over every line of `data`, attaching the line number and text
+/// to any error it returns.
+pub fn parse_lines<T, F>(data: &str, mut f: F) -> Result<Vec<T>>
+    where F: FnMut(&str) -> Result<T> {
+  data.lines()
+      .enumerate()
+      .map(|(idx, line)| {
+        f(line).map_err(|err| err.at(idx + 1, 1, line))
+      })
+      .collect()
+}
+
+/// 1-based column of `field` inside `line`, normally one of the pieces
+/// returned by e.g. `line.split(',')`. Anything else is looked for in `line`
+/// by value, and put at column 1 if it isn't there.
+pub fn column_of(line: &str, field: &str) -> usize {
+  let offset = (field.as_ptr() as usize).checked_sub(line.as_ptr() as usize)
+                                        .filter(|&offset| offset + field.len() <= line.len());
+  offset.or_else(|| line.find(field)).map_or(1, |offset| offset + 1)
+}
+
+fn parse_fields<'a, T, I>(line_no: usize, line: &'a str, fields: I) -> Result<Vec<T>>
+    where T: FromStr, I: Iterator<Item = &'a str> {
+  fields.map(|field| {
+          let trimmed = field.trim();
+          parse_num::<T>(trimmed).map_err(|err| err.at(line_no, column_of(line, trimmed), line))
+        })
+        .collect()
+}
+
+/// Parses each non-empty line as numbers separated by any amount of whitespace.
+pub fn whitespace_separated<T>(data: &str) -> Result<Vec<Vec<T>>>
+    where T: FromStr {
+  data.lines()
+      .enumerate()
+      .filter(|(_, line)| !line.trim().is_empty())
+      .map(|(idx, line)| parse_fields(idx + 1, line, line.split_whitespace()))
+      .collect()
+}
+
+/// Parses each non-empty line as comma separated numbers, e.g. `3,4,1,5`.
+pub fn comma_separated<T>(data: &str) -> Result<Vec<Vec<T>>>
+    where T: FromStr {
+  data.lines()
+      .enumerate()
+      .filter(|(_, line)| !line.trim().is_empty())
+      .map(|(idx, line)| parse_fields(idx + 1, line, line.split(',')))
+      .collect()
+}
+
+/// Parses each non-empty line as tab separated numbers.
+pub fn tab_separated<T>(data: &str) -> Result<Vec<Vec<T>>>
+    where T: FromStr {
+  data.lines()
+      .enumerate()
+      .filter(|(_, line)| !line.trim().is_empty())
+      .map(|(idx, line)| parse_fields(idx + 1, line, line.split('\t')))
+      .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  struct Location {
+    line: usize,
+    col: usize,
+    snippet: String,
+    msg: String,
+  }
+
+  fn parse_error(err: AocError) -> Location {
+    match err {
+      AocError::Parse { line, col, snippet, msg } => Location { line, col, snippet, msg },
+      err => panic!("Expected a parse error, got: {}", err),
+    }
+  }
+
+  #[test]
+  fn test_parse_num() {
+    assert_eq!(to_i64("-17").unwrap(), -17);
+    assert_eq!(to_usize("42").unwrap(), 42);
+    assert!(to_usize("-1").is_err());
+    assert_eq!(parse_num::<u8>("255").unwrap(), 255);
+  }
+
+  #[test]
+  fn test_digits() {
+    assert_eq!(digits("1122\n", 10).unwrap(), vec![1, 1, 2, 2]);
+    let err = parse_error(digits("12\n3x4", 10).unwrap_err());
+    assert_eq!((err.line, err.col), (2, 2));
+  }
+
+  #[test]
+  fn test_column_of() {
+    let line = "1,22,333";
+    let fields = line.split(',').collect::<Vec<_>>();
+    assert_eq!(column_of(line, fields[2]), 6);
+    assert_eq!(column_of(&line[2..], fields[0]), 1);
+    assert_eq!(column_of(line, "22"), 3);
+    assert_eq!(column_of(line, "4"), 1);
+  }
+
+  #[test]
+  fn test_whitespace_separated() {
+    let rows = whitespace_separated::<i64>("5 1 9 5\n7  5\t3\n").unwrap();
+    assert_eq!(rows, vec![vec![5, 1, 9, 5], vec![7, 5, 3]]);
+    let rows = whitespace_separated::<i64>("5 1 9 5\n\n7 5 3\n\n").unwrap();
+    assert_eq!(rows, vec![vec![5, 1, 9, 5], vec![7, 5, 3]]);
+  }
+
+  #[test]
+  fn test_whitespace_separated_error_location() {
+    let err = parse_error(whitespace_separated::<i64>("1 2\n3  bad 4").unwrap_err());
+    assert_eq!((err.line, err.col), (2, 4));
+    assert_eq!(err.snippet, "3  bad 4");
+  }
+
+  #[test]
+  fn test_comma_separated() {
+    let rows = comma_separated::<usize>("3, 4,1,5\n\n").unwrap();
+    assert_eq!(rows, vec![vec![3, 4, 1, 5]]);
+    let err = parse_error(comma_separated::<usize>("3,4,,5").unwrap_err());
+    assert_eq!((err.line, err.col), (1, 5));
+  }
+
+  #[test]
+  fn test_tab_separated() {
+    let rows = tab_separated::<i64>("0\t2\t7\t0\n").unwrap();
+    assert_eq!(rows, vec![vec![0, 2, 7, 0]]);
+  }
+
+  #[test]
+  fn test_parse_lines() {
+    let res = parse_lines("a\nbb\n", |s| Ok(s.len())).unwrap();
+    assert_eq!(res, vec![1, 2]);
+    let err = parse_lines("ok\nbad", |s| {
+      if s == "bad" { Err(invalid_input!("nope")) } else { Ok(()) }
+    }).unwrap_err();
+    let err = parse_error(err);
+    assert_eq!(err.line, 2);
+    assert_eq!(err.msg, "nope");
+  }
+}  // mod tests
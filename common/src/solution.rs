@@ -0,0 +1,15 @@
+use crate::Result;
+
+/// One day's puzzle. The input is parsed once and shared by both parts.
+pub trait Solution {
+  type Input;
+
+  /// Number of parts the puzzle has; the last day of a year only has one.
+  const PARTS: u32 = 2;
+
+  fn parse(data: &str) -> Result<Self::Input>;
+
+  fn part1(input: &Self::Input) -> Result<String>;
+
+  fn part2(input: &Self::Input) -> Result<String>;
+}
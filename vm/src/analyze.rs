@@ -0,0 +1,548 @@
+//! Works out what a program's loops do without running them. Registers are
+//! tracked as constants through the whole program and as affine expressions
+//! within a block, which is enough to find each loop's induction variables,
+//! the registers it leaves alone and, from its exit test, how many times it
+//! runs.
+//!
+//! There's no interval domain: a register is a constant, an affine
+//! expression or unknown. So trip counts are only found for loops that leave
+//! on a `jnz` once an affine counter reaches zero, as the coprocessor's do.
+//! Loops that test with `jgz`, as most of the duet's do, report unknown
+//! iterations.
+
+use aoc_common::{Result, invalid_input};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::bytecode::RegisterFile;
+use crate::disasm::{Disassembly, Succ};
+use crate::instr::{Instr, Val, jump_target};
+
+
+/// A constant plus a multiple of each register, the registers by name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Affine {
+  pub konst: i64,
+  pub terms: BTreeMap<String, i64>,
+}
+
+impl Affine {
+  pub fn konst(n: i64) -> Affine {
+    Affine { konst: n, terms: BTreeMap::new() }
+  }
+
+  pub fn reg(name: &str) -> Affine {
+    Affine { konst: 0, terms: BTreeMap::from([(name.to_owned(), 1)]) }
+  }
+
+  pub fn as_const(&self) -> Option<i64> {
+    if self.terms.is_empty() { Some(self.konst) } else { None }
+  }
+
+  // self + k * other, or None on overflow.
+  fn plus(&self, k: i64, other: &Affine) -> Option<Affine> {
+    let mut sum = self.clone();
+    sum.konst = sum.konst.checked_add(k.checked_mul(other.konst)?)?;
+    for (name, &coeff) in &other.terms {
+      let term = sum.terms.entry(name.clone()).or_insert(0);
+      *term = term.checked_add(k.checked_mul(coeff)?)?;
+      if *term == 0 {
+        sum.terms.remove(name);
+      }
+    }
+    Some(sum)
+  }
+
+  fn scale(&self, k: i64) -> Option<Affine> {
+    Affine::konst(0).plus(k, self)
+  }
+
+  fn mul(&self, other: &Affine) -> Option<Affine> {
+    match (self.as_const(), other.as_const()) {
+      (Some(k), _) => other.scale(k),
+      (None, Some(k)) => self.scale(k),
+      (None, None) => None,
+    }
+  }
+}
+
+impl fmt::Display for Affine {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut first = true;
+    for (name, &coeff) in &self.terms {
+      let sign = match (first, coeff < 0) {
+        (true, true) => "-",
+        (true, false) => "",
+        (false, true) => " - ",
+        (false, false) => " + ",
+      };
+      match coeff.unsigned_abs() {
+        1 => write!(f, "{}{}", sign, name)?,
+        n => write!(f, "{}{}*{}", sign, n, name)?,
+      }
+      first = false;
+    }
+    match (first, self.konst) {
+      (true, n) => write!(f, "{}", n),
+      (false, 0) => Ok(()),
+      (false, n) if n < 0 => write!(f, " - {}", n.unsigned_abs()),
+      (false, n) => write!(f, " + {}", n),
+    }
+  }
+}
+
+// What's known about each register by slot; None when nothing is.
+type Regs = Vec<Option<Affine>>;
+
+fn val(file: &RegisterFile, regs: &Regs, val: &Val) -> Option<Affine> {
+  match *val {
+    Val::Reg(ref name) => regs[file.slot(name)?].clone(),
+    Val::Num(n) => Some(Affine::konst(n)),
+    Val::None => None,
+  }
+}
+
+fn written(file: &RegisterFile, instr: &Instr) -> Option<usize> {
+  match *instr {
+    Instr::Add(Val::Reg(ref x), _) | Instr::Mod(Val::Reg(ref x), _)
+      | Instr::Mul(Val::Reg(ref x), _) | Instr::Set(Val::Reg(ref x), _)
+      | Instr::Sub(Val::Reg(ref x), _) | Instr::Rcv(Val::Reg(ref x)) => file.slot(x),
+    _ => None,
+  }
+}
+
+fn eval(file: &RegisterFile, instr: &Instr, regs: &mut Regs) {
+  let val = |regs: &Regs, y| val(file, regs, y);
+  let x = match written(file, instr) {
+    Some(x) => x,
+    None => return,
+  };
+  let old = regs[x].clone();
+  regs[x] = match *instr {
+    Instr::Set(_, ref y) => val(regs, y),
+    Instr::Add(_, ref y) => old.zip(val(regs, y)).and_then(|(x, y)| x.plus(1, &y)),
+    Instr::Sub(_, ref y) => old.zip(val(regs, y)).and_then(|(x, y)| x.plus(-1, &y)),
+    Instr::Mul(_, ref y) => old.zip(val(regs, y)).and_then(|(x, y)| x.mul(&y)),
+    Instr::Mod(_, ref y) => match (old.and_then(|x| x.as_const()),
+                                   val(regs, y).and_then(|y| y.as_const())) {
+      (Some(x), Some(y)) => x.checked_rem(y).map(Affine::konst),
+      _ => None,
+    },
+    _ => None,
+  };
+}
+
+// Where control can go after the instruction, given what's known.
+fn succs(file: &RegisterFile, pc: usize, instr: &Instr, regs: &Regs, len: usize) -> Vec<i64> {
+  let val = |y| val(file, regs, y);
+  let (cond, offset, taken): (_, _, fn(i64) -> bool) = match *instr {
+    Instr::Jgz(ref x, ref y) => (x, y, |n| n > 0),
+    Instr::Jnz(ref x, ref y) => (x, y, |n| n != 0),
+    _ => return vec![pc as i64 + 1],
+  };
+  let to = match val(offset).and_then(|y| y.as_const()) {
+    Some(n) => jump_target(pc, n),
+    None => return (0..=len as i64).collect(),
+  };
+  match val(cond).and_then(|x| x.as_const()) {
+    Some(n) if taken(n) => vec![to],
+    Some(_) => vec![pc as i64 + 1],
+    None => vec![pc as i64 + 1, to],
+  }
+}
+
+fn join(into: &mut Option<Regs>, regs: &Regs) -> bool {
+  match *into {
+    None => {
+      *into = Some(regs.clone());
+      true
+    },
+    Some(ref mut old) => {
+      let mut changed = false;
+      for (old, new) in old.iter_mut().zip(regs) {
+        if old.is_some() && old != new {
+          *old = None;
+          changed = true;
+        }
+      }
+      changed
+    },
+  }
+}
+
+/// A register that changes by the same amount on every iteration.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Induction {
+  pub reg: String,
+  /// Its value on entering the loop, in terms of itself when not a constant.
+  pub from: Affine,
+  /// Its value on the last iteration, when the number of iterations is known.
+  pub to: Option<Affine>,
+  pub step: i64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoopSummary {
+  pub start: usize,
+  pub end: usize,
+  pub depth: usize,
+  /// How many times the body runs on each entry to the loop.
+  pub trips: Option<Affine>,
+  pub inductions: Vec<Induction>,
+  /// Registers the loop reads but never writes, with their value if it's
+  /// always the same.
+  pub invariants: Vec<(String, Option<i64>)>,
+}
+
+impl fmt::Display for LoopSummary {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(f, "loop L{}..L{}, depth {}", self.start, self.end - 1, self.depth)?;
+    match self.trips {
+      Some(ref trips) if trips.as_const() == Some(1) => writeln!(f, "  1 iteration")?,
+      Some(ref trips) => writeln!(f, "  {} iterations", trips)?,
+      None => writeln!(f, "  unknown iterations")?,
+    }
+    for ind in &self.inductions {
+      write!(f, "  {} from {}", ind.reg, ind.from)?;
+      if let Some(ref to) = ind.to {
+        write!(f, " to {}", to)?;
+      }
+      writeln!(f, " by {}", ind.step)?;
+    }
+    for (name, n) in &self.invariants {
+      match *n {
+        Some(n) => writeln!(f, "  {} = {} throughout", name, n)?,
+        None => writeln!(f, "  {} unchanged", name)?,
+      }
+    }
+    Ok(())
+  }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Analysis {
+  pub loops: Vec<LoopSummary>,
+}
+
+impl fmt::Display for Analysis {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for summary in &self.loops {
+      write!(f, "{}", summary)?;
+    }
+    Ok(())
+  }
+}
+
+struct Analyzer<'a> {
+  instrs: &'a [Instr],
+  file: RegisterFile,
+  dis: Disassembly<'a>,
+  /// What's known on reaching each pc, or None if it can't be reached.
+  states: Vec<Option<Regs>>,
+}
+
+impl<'a> Analyzer<'a> {
+  fn out(&self, pc: usize) -> Option<Regs> {
+    let mut regs = self.states[pc].clone()?;
+    eval(&self.file, &self.instrs[pc], &mut regs);
+    Some(regs)
+  }
+
+  fn succs(&self, pc: usize) -> Vec<i64> {
+    match self.states[pc] {
+      Some(ref regs) => succs(&self.file, pc, &self.instrs[pc], regs, self.instrs.len()),
+      None => Vec::new(),
+    }
+  }
+
+  // Constant propagation to a fixed point. Each register can only go from
+  // unknown to a constant to not constant, so it soon gets there.
+  fn propagate(&mut self, init: Regs) {
+    let len = self.instrs.len();
+    let mut work = Vec::new();
+    if len > 0 {
+      self.states[0] = Some(init);
+      work.push(0);
+    }
+    while let Some(pc) = work.pop() {
+      let regs = self.out(pc).expect("Only reached pcs are queued");
+      for to in self.succs(pc) {
+        if (0..len as i64).contains(&to) && join(&mut self.states[to as usize], &regs) {
+          work.push(to as usize);
+        }
+      }
+    }
+  }
+
+  // Every register standing for itself.
+  fn symbols(&self) -> Regs {
+    self.file.names().iter().map(|name| Some(Affine::reg(name))).collect()
+  }
+
+  fn block_of(&self, pc: usize) -> usize {
+    self.dis.blocks.iter().position(|b| b.start <= pc && pc < b.end).unwrap()
+  }
+
+  // Whether every path from `head` to `tail` goes through `block`.
+  fn dominates(&self, block: usize, head: usize, tail: usize) -> bool {
+    let mut seen = vec![false; self.dis.blocks.len()];
+    let mut work = vec![head];
+    while let Some(idx) = work.pop() {
+      if idx == block || seen[idx] || idx < head || idx > tail {
+        continue;
+      }
+      seen[idx] = true;
+      work.extend(self.dis.blocks[idx].succs.iter().filter_map(|succ| match *succ {
+        Succ::Block(to) => Some(to),
+        _ => None,
+      }));
+    }
+    !seen[tail]
+  }
+
+  fn summarize(&self, head: usize, tail: usize, depth: usize) -> LoopSummary {
+    let (start, end) = (self.dis.blocks[head].start, self.dis.blocks[tail].end);
+    let body = &self.instrs[start..end];
+    let len = self.file.len();
+    let mut read = vec![false; len];
+    let mut writes = vec![0; len];
+    for instr in body {
+      for val in instr.operands() {
+        if let Val::Reg(ref name) = *val {
+          read[self.file.slot(name).unwrap()] = true;
+        }
+      }
+      if let Some(x) = written(&self.file, instr) {
+        writes[x] += 1;
+      }
+    }
+
+    // What the loop starts with is whatever reaches its head from outside.
+    let mut entry = None;
+    for pc in (0..start).chain(end..self.instrs.len()) {
+      if self.succs(pc).contains(&(start as i64)) {
+        join(&mut entry, &self.out(pc).unwrap());
+      }
+    }
+    let entry = entry.unwrap_or_else(|| vec![None; len]);
+
+    // Induction variables are only written in the tail block, which runs
+    // exactly once an iteration, and only by adding a constant.
+    let tail_start = self.dis.blocks[tail].start;
+    let mut effect = self.symbols();
+    let mut tail_writes = vec![0; len];
+    for instr in &self.instrs[tail_start..end] {
+      eval(&self.file, instr, &mut effect);
+      if let Some(x) = written(&self.file, instr) {
+        tail_writes[x] += 1;
+      }
+    }
+    let mut steps = BTreeMap::new();
+    for idx in 0..len {
+      if writes[idx] == 0 || writes[idx] != tail_writes[idx] {
+        continue;
+      }
+      let reg = Affine::reg(self.file.name(idx));
+      let step = effect[idx].as_ref().and_then(|e| e.plus(-1, &reg)).and_then(|e| e.as_const());
+      if let Some(step) = step.filter(|&s| s != 0) {
+        let from = entry[idx].clone().unwrap_or(reg);
+        steps.insert(idx, (from, step));
+      }
+    }
+
+    let trips = self.trips(head, tail, &steps, &writes, &entry);
+    let inductions = steps.into_iter()
+                          .map(|(idx, (from, step))| {
+                            let to = trips.as_ref()
+                                          .and_then(|n| n.plus(-1, &Affine::konst(1)))
+                                          .and_then(|k| from.plus(step, &k));
+                            Induction { reg: self.file.name(idx).to_owned(), from, to, step }
+                          })
+                          .collect();
+    let invariants = (0..len).filter(|&idx| read[idx] && writes[idx] == 0)
+                             .map(|idx| {
+                               let n = entry[idx].as_ref().and_then(|e| e.as_const());
+                               (self.file.name(idx).to_owned(), n)
+                             })
+                             .collect();
+    LoopSummary { start, end, depth, trips, inductions, invariants }
+  }
+
+  // The loop's only way out must be a `jnz` that every iteration runs once
+  // and that falls through to leave, so it leaves once its test is zero.
+  // Written as C + K * k for iteration k, the test gives the iterations.
+  fn trips(&self, head: usize, tail: usize, steps: &BTreeMap<usize, (Affine, i64)>,
+           writes: &[usize], entry: &Regs) -> Option<Affine> {
+    let (start, end) = (self.dis.blocks[head].start, self.dis.blocks[tail].end);
+    // Constants can show the way back is never taken.
+    if !self.succs(end - 1).contains(&(start as i64)) {
+      return Some(Affine::konst(1));
+    }
+    let outside = |pc: i64| !(start as i64..end as i64).contains(&pc);
+    // Falling through to a jump that's always taken out leaves it too.
+    let leaves = |pc: i64| {
+      let succs = if outside(pc) { Vec::new() } else { self.succs(pc as usize) };
+      outside(pc) || !succs.is_empty() && succs.iter().all(|&to| outside(to))
+    };
+    let exits = (start..end).filter(|&pc| self.succs(pc).iter().any(|&to| leaves(to)))
+                            .collect::<Vec<_>>();
+    let pc = match *exits.as_slice() {
+      [pc] => pc,
+      [pc, next] if next == pc + 1 => pc,
+      _ => return None,
+    };
+    let cond = match (&self.instrs[pc], self.succs(pc).as_slice()) {
+      (Instr::Jnz(x, _), &[next, to]) if leaves(next) && !leaves(to) => x,
+      _ => return None,
+    };
+    let block = self.block_of(pc);
+    let nested = self.dis.loops.iter().any(|l| {
+      (l.head, l.tail) != (head, tail) && head <= l.head && l.tail <= tail
+        && (l.head..=l.tail).contains(&block)
+    });
+    if nested || !self.dominates(block, head, tail) {
+      return None;
+    }
+    let mut regs = self.symbols();
+    for instr in &self.instrs[self.dis.blocks[block].start..pc] {
+      eval(&self.file, instr, &mut regs);
+    }
+    let test = val(&self.file, &regs, cond)?;
+    let (mut c, mut k) = (Affine::konst(test.konst), 0i64);
+    for (name, &coeff) in &test.terms {
+      let idx = self.file.slot(name)?;
+      if let Some((from, step)) = steps.get(&idx) {
+        c = c.plus(coeff, from)?;
+        k = k.checked_add(coeff.checked_mul(*step)?)?;
+      } else if writes[idx] == 0 {
+        let value = entry[idx].clone().filter(|e| e.as_const().is_some());
+        c = c.plus(coeff, &value.unwrap_or_else(|| Affine::reg(name)))?;
+      } else {
+        return None;
+      }
+    }
+    // The last iteration is the k where C + K * k == 0.
+    let last = match (c.as_const(), k) {
+      (_, 0) => return None,
+      (Some(c), k) => match (c.checked_rem(k), c.checked_neg()?.checked_div(k)) {
+        (Some(0), Some(n)) if n >= 0 => Affine::konst(n),
+        _ => return None,
+      },
+      (None, 1 | -1) => c.scale(-k)?,
+      (None, _) => return None,
+    };
+    last.plus(1, &Affine::konst(1))
+  }
+}
+
+impl Analysis {
+  /// Analyzes a program started with the given registers, the rest zero.
+  pub fn new(instrs: &[Instr], regs: &[(String, i64)]) -> Result<Analysis> {
+    let file = RegisterFile::of(instrs);
+    let mut init = vec![Some(Affine::konst(0)); file.len()];
+    for (name, n) in regs {
+      let idx = file.slot(name).ok_or_else(|| invalid_input!("Unknown register: {}", name))?;
+      init[idx] = Some(Affine::konst(*n));
+    }
+    let mut analyzer = Analyzer {
+      instrs,
+      file,
+      dis: Disassembly::new(instrs),
+      states: vec![None; instrs.len()],
+    };
+    analyzer.propagate(init);
+    let loops = analyzer.dis
+                        .loops
+                        .iter()
+                        .filter(|l| analyzer.states[analyzer.dis.blocks[l.head].start].is_some())
+                        .map(|l| analyzer.summarize(l.head, l.tail, l.depth))
+                        .collect();
+    Ok(Analysis { loops })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::dialect::COPROCESSOR;
+
+  fn analyze(source: &str, regs: &[(&str, i64)]) -> String {
+    let regs = regs.iter().map(|&(name, n)| (name.to_owned(), n)).collect::<Vec<_>>();
+    Analysis::new(&COPROCESSOR.parse(source).unwrap(), &regs).unwrap().to_string()
+  }
+
+  #[test]
+  fn test_countdowns() {
+    let source = "set a 3\nset b 2\nsub b 1\njnz b -1\nsub a 1\njnz a -4\njnz 1 2\nset c 1";
+    assert_eq!(analyze(source, &[]), "\
+loop L1..L5, depth 1
+  3 iterations
+  a from 3 to 1 by -1
+loop L2..L3, depth 2
+  2 iterations
+  b from 2 to 1 by -1
+");
+  }
+
+  // The bound of the outer loop depends on a, and the inner loop runs up to
+  // the outer one's induction variable.
+  const NESTED: &str = "\
+set b 10
+jnz a 2
+set b 20
+set c 0
+set d 5
+set e 2
+sub e -1
+set g e
+sub g d
+jnz g -3
+sub d -1
+set g d
+sub g b
+jnz g -8";
+
+  #[test]
+  fn test_symbolic_bounds() {
+    assert_eq!(analyze(NESTED, &[("a", 1)]), "\
+loop L5..L13, depth 1
+  5 iterations
+  d from 5 to 9 by 1
+  b = 10 throughout
+loop L6..L9, depth 2
+  d - 2 iterations
+  e from 2 to d - 1 by 1
+  d unchanged
+");
+    assert!(analyze(NESTED, &[]).contains("15 iterations\n  d from 5 to 19 by 1\n"));
+  }
+
+  #[test]
+  fn test_empty_program() {
+    assert_eq!(Analysis::new(&[], &[]).unwrap(), Analysis { loops: Vec::new() });
+  }
+
+  #[test]
+  fn test_trips_that_overflow() {
+    let source = "set a -9223372036854775807\nsub a 1\njnz a -1";
+    assert_eq!(analyze(source, &[]), "\
+loop L1..L2, depth 1
+  unknown iterations
+  a from -9223372036854775807 by -1
+");
+  }
+
+  #[test]
+  fn test_far_jumps_halt() {
+    let instrs = [Instr::Jnz(Val::Reg("a".to_owned()), Val::Num(i64::MAX))];
+    assert_eq!(Analysis::new(&instrs, &[("a".to_owned(), 1)]).unwrap().to_string(), "");
+  }
+
+  #[test]
+  fn test_affine_display() {
+    let e = Affine::reg("b").plus(-3, &Affine::reg("d")).unwrap()
+                            .plus(1, &Affine::konst(-4)).unwrap();
+    assert_eq!(e.to_string(), "b - 3*d - 4");
+    assert_eq!(Affine::konst(0).plus(-1, &Affine::reg("a")).unwrap().to_string(), "-a");
+  }
+}  // mod tests
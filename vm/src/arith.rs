@@ -0,0 +1,195 @@
+//! What the arithmetic instructions do at the edges: results that don't fit
+//! in 64 bits, and `mod` with a negative or zero operand.
+
+use aoc_common::{Result, invalid_input};
+use num_bigint::{BigInt, Sign};
+
+
+/// What happens when a result doesn't fit in 64 bits.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Overflow {
+  /// It's an error.
+  #[default]
+  Checked,
+  /// It wraps around, as two's complement does.
+  Wrapping,
+  /// The register grows to hold it.
+  Big,
+}
+
+impl Overflow {
+  pub fn parse(s: &str) -> Result<Overflow> {
+    Ok(match s {
+      "checked" => Overflow::Checked,
+      "wrapping" => Overflow::Wrapping,
+      "big" => Overflow::Big,
+      _ => return Err(invalid_input!("Unknown overflow mode: {}", s)),
+    })
+  }
+}
+
+/// Which sign `mod` gives a remainder.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Modulo {
+  /// The dividend's, as Rust's `%` does.
+  #[default]
+  Truncating,
+  /// Never negative.
+  Euclidean,
+}
+
+impl Modulo {
+  pub fn parse(s: &str) -> Result<Modulo> {
+    Ok(match s {
+      "truncating" => Modulo::Truncating,
+      "euclidean" => Modulo::Euclidean,
+      _ => return Err(invalid_input!("Unknown mod mode: {}", s)),
+    })
+  }
+}
+
+/// How a `CPU` does arithmetic. Whatever the mode, `mod` by zero is an error.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Arith {
+  pub overflow: Overflow,
+  pub modulo: Modulo,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum BinOp {
+  Add,
+  Mod,
+  Mul,
+  Sub,
+}
+
+impl BinOp {
+  fn symbol(self) -> &'static str {
+    match self {
+      BinOp::Add => "+",
+      BinOp::Mod => "%",
+      BinOp::Mul => "*",
+      BinOp::Sub => "-",
+    }
+  }
+}
+
+/// A register's value, when it might not fit in 64 bits.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Word {
+  Small(i64),
+  Big(BigInt),
+}
+
+impl Word {
+  // Keeps values that fit in 64 bits small.
+  pub(crate) fn new(n: BigInt) -> Word {
+    i64::try_from(&n).map(Word::Small).unwrap_or(Word::Big(n))
+  }
+}
+
+impl From<Word> for BigInt {
+  fn from(word: Word) -> BigInt {
+    match word {
+      Word::Small(n) => BigInt::from(n),
+      Word::Big(n) => n,
+    }
+  }
+}
+
+impl Arith {
+  // x op y, or None if it doesn't fit.
+  fn exact(self, op: BinOp, x: i64, y: i64) -> Option<i64> {
+    match op {
+      BinOp::Add => x.checked_add(y),
+      BinOp::Mul => x.checked_mul(y),
+      BinOp::Sub => x.checked_sub(y),
+      // Only i64::MIN % -1 overflows, and not in its remainder.
+      BinOp::Mod if y == -1 => Some(0),
+      BinOp::Mod => match self.modulo {
+        Modulo::Truncating => x.checked_rem(y),
+        Modulo::Euclidean => x.checked_rem_euclid(y),
+      },
+    }
+  }
+
+  fn wrapping(self, op: BinOp, x: i64, y: i64) -> i64 {
+    match op {
+      BinOp::Add => x.wrapping_add(y),
+      BinOp::Mul => x.wrapping_mul(y),
+      BinOp::Sub => x.wrapping_sub(y),
+      BinOp::Mod => match self.modulo {
+        Modulo::Truncating => x.wrapping_rem(y),
+        Modulo::Euclidean => x.wrapping_rem_euclid(y),
+      },
+    }
+  }
+
+  /// Works out `x op y` for the registers' usual 64 bits.
+  pub(crate) fn apply(self, op: BinOp, x: i64, y: i64) -> Result<Word> {
+    if op == BinOp::Mod && y == 0 {
+      return Err(invalid_input!("Modulo by zero"));
+    }
+    match (self.overflow, self.exact(op, x, y)) {
+      (_, Some(n)) => Ok(Word::Small(n)),
+      (Overflow::Checked, None) => {
+        Err(invalid_input!("Overflow: {} {} {} doesn't fit in 64 bits", x, op.symbol(), y))
+      },
+      (Overflow::Wrapping, None) => Ok(Word::Small(self.wrapping(op, x, y))),
+      (Overflow::Big, None) => self.apply_big(op, BigInt::from(x), &BigInt::from(y)),
+    }
+  }
+
+  /// Works out `x op y` where either might already be too big for 64 bits,
+  /// which only happens with `Overflow::Big`.
+  pub(crate) fn apply_big(self, op: BinOp, x: BigInt, y: &BigInt) -> Result<Word> {
+    Ok(Word::new(match op {
+      BinOp::Add => x + y,
+      BinOp::Mul => x * y,
+      BinOp::Sub => x - y,
+      BinOp::Mod if y.sign() == Sign::NoSign => return Err(invalid_input!("Modulo by zero")),
+      BinOp::Mod => {
+        let rem = x % y;
+        match (self.modulo, rem.sign(), y.sign()) {
+          (Modulo::Euclidean, Sign::Minus, Sign::Minus) => rem - y,
+          (Modulo::Euclidean, Sign::Minus, _) => rem + y,
+          _ => rem,
+        }
+      },
+    }))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const CHECKED: Arith = Arith { overflow: Overflow::Checked, modulo: Modulo::Truncating };
+  const WRAPPING: Arith = Arith { overflow: Overflow::Wrapping, modulo: Modulo::Truncating };
+  const BIG: Arith = Arith { overflow: Overflow::Big, modulo: Modulo::Euclidean };
+
+  #[test]
+  fn test_overflow() {
+    let err = CHECKED.apply(BinOp::Mul, i64::MAX, 2).unwrap_err();
+    assert_eq!(err.to_string(), "Overflow: 9223372036854775807 * 2 doesn't fit in 64 bits");
+    assert_eq!(WRAPPING.apply(BinOp::Mul, i64::MAX, 2).unwrap(), Word::Small(-2));
+    let big = BIG.apply(BinOp::Mul, i64::MAX, 2).unwrap();
+    assert_eq!(big, Word::Big(BigInt::from(i64::MAX) * 2));
+    // Back down to 64 bits, it's small again.
+    let half = BIG.apply_big(BinOp::Sub, big.into(), &BigInt::from(i64::MAX)).unwrap();
+    assert_eq!(half, Word::Small(i64::MAX));
+  }
+
+  #[test]
+  fn test_modulo() {
+    assert_eq!(CHECKED.apply(BinOp::Mod, -7, 3).unwrap(), Word::Small(-1));
+    assert_eq!(BIG.apply(BinOp::Mod, -7, 3).unwrap(), Word::Small(2));
+    assert_eq!(BIG.apply(BinOp::Mod, -7, -3).unwrap(), Word::Small(2));
+    assert_eq!(CHECKED.apply(BinOp::Mod, i64::MIN, -1).unwrap(), Word::Small(0));
+    let huge = BigInt::from(i64::MIN) * 4 - 1;
+    assert_eq!(BIG.apply_big(BinOp::Mod, huge, &BigInt::from(4)).unwrap(), Word::Small(3));
+    for arith in [CHECKED, WRAPPING, BIG] {
+      assert_eq!(arith.apply(BinOp::Mod, 1, 0).unwrap_err().to_string(), "Modulo by zero");
+    }
+  }
+}  // mod tests
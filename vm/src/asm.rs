@@ -0,0 +1,152 @@
+//! An assembler for writing programs by hand. On top of the plain one
+//! instruction per line it accepts `;` comments, `name:` labels that jumps
+//! can target by name, and `.equ name value` constants, and lowers it all to
+//! the same instructions.
+
+use aoc_common::{AocError, Result, column_of, to_i64};
+use std::collections::HashMap;
+
+use crate::dialect::{Dialect, Registers, is_ident};
+use crate::instr::{Instr, Op};
+use crate::verify::{check_jump, combine};
+
+
+// An instruction still to be assembled, with the source line it's on.
+struct Pending<'a> {
+  line_no: usize,
+  line: &'a str,
+  fields: Vec<&'a str>,
+}
+
+impl Dialect {
+  /// Assembles a program, reporting every problem with its line. Labels can
+  /// share a line with an instruction or have one to themselves, and name
+  /// the instruction after them; a jump to one is lowered to the offset.
+  /// Where any name can be a register, a label or constant hides the
+  /// register it names.
+  pub fn assemble(&self, source: &str) -> Result<Vec<Instr>> {
+    let mut labels = HashMap::new();
+    let mut consts = HashMap::new();
+    let mut pending = Vec::new();
+    let mut problems = Vec::new();
+    for (idx, line) in source.lines().enumerate() {
+      let line_no = idx + 1;
+      let problem = |field: &str, msg: String| {
+        AocError::parse(line_no, column_of(line, field), line, &msg)
+      };
+      let code = line.split(';').next().unwrap_or("");
+      let mut fields = code.split_whitespace().collect::<Vec<_>>();
+      // A name already taken can't be given to anything else.
+      let taken = |name: &str, labels: &HashMap<_, _>, consts: &HashMap<_, _>| {
+        if !is_ident(name) {
+          Some(format!("Invalid name: {}", name))
+        } else if self.registers != Registers::Any && self.valid_reg(name) {
+          Some(format!("{} is a register", name))
+        } else if labels.contains_key(name) || consts.contains_key(name) {
+          Some(format!("{} is already defined", name))
+        } else {
+          None
+        }
+      };
+      while let Some(name) = fields.first().and_then(|field| field.strip_suffix(':')) {
+        match taken(name, &labels, &consts) {
+          Some(msg) => problems.push(problem(fields[0], msg)),
+          None => {
+            labels.insert(name, pending.len());
+          },
+        }
+        fields.remove(0);
+      }
+      match fields.as_slice() {
+        [] => {},
+        [".equ", name, value] => {
+          let n = to_i64(value).ok().or_else(|| consts.get(value).copied());
+          match (taken(name, &labels, &consts), n) {
+            (Some(msg), _) => problems.push(problem(name, msg)),
+            (None, Some(n)) => {
+              consts.insert(*name, n);
+            },
+            (None, None) => problems.push(problem(value, format!("Invalid value: {}", value))),
+          }
+        },
+        [".equ", ..] => {
+          problems.push(problem(fields[0], "Expected .equ <name> <value>".to_owned()));
+        },
+        [directive, ..] if directive.starts_with('.') => {
+          problems.push(problem(directive, format!("Unknown directive: {}", directive)));
+        },
+        _ => pending.push(Pending { line_no, line, fields }),
+      }
+    }
+
+    let len = pending.len();
+    let mut instrs = Vec::new();
+    for (pc, Pending { line_no, line, fields }) in pending.into_iter().enumerate() {
+      let jump = matches!(self.op(&fields[0].to_lowercase()), Some(Op::Jgz | Op::Jnz));
+      // Labels only mean something as where a jump goes.
+      let resolve = |idx: usize, name: &str| match labels.get(name) {
+        Some(&to) if jump && idx == 1 => Some(to as i64 - pc as i64),
+        _ => consts.get(name).copied(),
+      };
+      match self.check_fields(line_no, line, &fields, &resolve) {
+        Ok(instr) => {
+          if let Some(msg) = check_jump(pc, len, &instr) {
+            problems.push(AocError::parse(line_no, column_of(line, fields[2]), line, &msg));
+          }
+          instrs.push(instr);
+        },
+        Err(errs) => problems.extend(errs),
+      }
+    }
+    problems.sort_by_key(|err| match *err {
+      AocError::Parse { line, col, .. } => (line, col),
+      _ => (0, 0),
+    });
+    combine(instrs, problems)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::dialect::{COPROCESSOR, DUET};
+
+  const COUNTDOWN: &str = "\
+.equ start 3          ; how far to count
+        set a start
+loop:   sub a 1
+        jnz a loop
+done:";
+
+  #[test]
+  fn test_assemble() {
+    let instrs = COPROCESSOR.assemble(COUNTDOWN).unwrap();
+    let lines = instrs.iter().map(|instr| instr.to_string()).collect::<Vec<_>>();
+    assert_eq!(lines, vec!["set a 3", "sub a 1", "jnz a -1"]);
+    // Plain programs assemble to themselves.
+    assert_eq!(COPROCESSOR.assemble(&lines.join("\n")).unwrap(), instrs);
+  }
+
+  #[test]
+  fn test_forward_labels_and_halt() {
+    let instrs = DUET.assemble("jgz a end\nsnd a\nend: ; nothing after").unwrap();
+    assert_eq!(instrs[0].to_string(), "jgz a 2");
+  }
+
+  #[test]
+  fn test_problems() {
+    let err = COPROCESSOR.assemble("\
+a: set b 1
+.equ n x
+loop: set c loop
+loop: jnz c nowhere
+.org 5").unwrap_err();
+    assert_eq!(err.to_string(), "\
+6 problems in the program:
+line 1, col 1: a is a register (in 'a: set b 1')
+line 2, col 8: Invalid value: x (in '.equ n x')
+line 3, col 13: Unknown register: loop (in 'loop: set c loop')
+line 4, col 1: loop is already defined (in 'loop: jnz c nowhere')
+line 4, col 13: Unknown register: nowhere (in 'loop: jnz c nowhere')
+line 5, col 1: Unknown directive: .org (in '.org 5')");
+  }
+}  // mod tests
@@ -0,0 +1,211 @@
+use aoc_common::{AocError, Result, invalid_input};
+
+use crate::instr::{Instr, Val, jump_target};
+use crate::optimize::Superinstr;
+
+
+/// The names of a program's registers, each one's slot its position.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RegisterFile {
+  names: Vec<String>,
+}
+
+impl RegisterFile {
+  pub fn new(names: Vec<String>) -> RegisterFile {
+    RegisterFile { names }
+  }
+
+  /// The registers the instructions name, in order.
+  pub fn of(instrs: &[Instr]) -> RegisterFile {
+    let mut names = instrs.iter()
+                          .flat_map(|instr| instr.operands())
+                          .filter_map(|val| match *val {
+                            Val::Reg(ref name) => Some(name.clone()),
+                            _ => None,
+                          })
+                          .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    RegisterFile { names }
+  }
+
+  pub fn names(&self) -> &[String] {
+    &self.names
+  }
+
+  pub fn slot(&self, name: &str) -> Option<usize> {
+    self.names.iter().position(|n| n == name)
+  }
+
+  pub fn name(&self, slot: usize) -> &str {
+    &self.names[slot]
+  }
+
+  pub fn len(&self) -> usize {
+    self.names.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.names.is_empty()
+  }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Arg {
+  Reg(usize),
+  Imm(i64),
+}
+
+/// Where a jump goes when it's taken.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Target {
+  /// An absolute pc, resolved from an immediate offset.
+  To(i64),
+  /// An offset only known at run time.
+  By(usize),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Code {
+  Add(usize, Arg),
+  Mod(usize, Arg),
+  Mul(usize, Arg),
+  Set(usize, Arg),
+  Sub(usize, Arg),
+  Jgz(Arg, Target),
+  Jnz(Arg, Target),
+  /// A jump whose condition always holds.
+  Jmp(i64),
+  Rcv(usize),
+  Recover(Arg),
+  Snd(Arg),
+  /// A loop the optimizer replaced, indexing `Program::superinstrs`.
+  Idiom(usize),
+}
+
+/// A program compiled for the `CPU`, keeping the instructions it came from.
+#[derive(Debug)]
+pub struct Program {
+  instrs: Vec<Instr>,
+  registers: RegisterFile,
+  pub(crate) code: Vec<Code>,
+  pub(crate) superinstrs: Vec<Superinstr>,
+}
+
+fn arg(regs: &RegisterFile, val: &Val) -> Result<Arg> {
+  match *val {
+    Val::Reg(ref name) => {
+      regs.slot(name).map(Arg::Reg).ok_or_else(|| invalid_input!("Unknown register: {}", name))
+    },
+    Val::Num(n) => Ok(Arg::Imm(n)),
+    Val::None => Err(invalid_input!("Missing operand")),
+  }
+}
+
+fn reg(regs: &RegisterFile, val: &Val) -> Result<usize> {
+  match arg(regs, val)? {
+    Arg::Reg(idx) => Ok(idx),
+    Arg::Imm(_) => Err(invalid_input!("Value not a register: {}", val)),
+  }
+}
+
+fn target(regs: &RegisterFile, pc: usize, offset: &Val) -> Result<Target> {
+  Ok(match arg(regs, offset)? {
+    Arg::Reg(idx) => Target::By(idx),
+    Arg::Imm(n) => Target::To(jump_target(pc, n)),
+  })
+}
+
+fn compile_one(regs: &RegisterFile, pc: usize, instr: &Instr) -> Result<Code> {
+  let arg = |val| arg(regs, val);
+  let reg = |val| reg(regs, val);
+  Ok(match *instr {
+    Instr::Add(ref x, ref y) => Code::Add(reg(x)?, arg(y)?),
+    Instr::Mod(ref x, ref y) => Code::Mod(reg(x)?, arg(y)?),
+    Instr::Mul(ref x, ref y) => Code::Mul(reg(x)?, arg(y)?),
+    Instr::Set(ref x, ref y) => Code::Set(reg(x)?, arg(y)?),
+    Instr::Sub(ref x, ref y) => Code::Sub(reg(x)?, arg(y)?),
+    Instr::Jgz(ref x, ref y) => match (arg(x)?, target(regs, pc, y)?) {
+      (Arg::Imm(n), Target::To(to)) if n > 0 => Code::Jmp(to),
+      (cond, target) => Code::Jgz(cond, target),
+    },
+    Instr::Jnz(ref x, ref y) => match (arg(x)?, target(regs, pc, y)?) {
+      (Arg::Imm(n), Target::To(to)) if n != 0 => Code::Jmp(to),
+      (cond, target) => Code::Jnz(cond, target),
+    },
+    Instr::Rcv(ref x) => Code::Rcv(reg(x)?),
+    Instr::Recover(ref x) => Code::Recover(arg(x)?),
+    Instr::Snd(ref x) => Code::Snd(arg(x)?),
+  })
+}
+
+impl Program {
+  /// Resolves every register to its slot and every constant jump to its
+  /// target. Errors carry the 1-based line of the instruction.
+  pub fn compile(instrs: &[Instr]) -> Result<Program> {
+    Program::with_registers(instrs, RegisterFile::of(instrs))
+  }
+
+  /// Like `compile`, with the registers given rather than those the
+  /// instructions name. Naming any other register is an error.
+  pub fn with_registers(instrs: &[Instr], registers: RegisterFile) -> Result<Program> {
+    let code = instrs.iter()
+                     .enumerate()
+                     .map(|(pc, instr)| {
+                       compile_one(&registers, pc, instr).map_err(|err| {
+                         AocError::parse(pc + 1, 1, &instr.to_string(), &err.to_string())
+                       })
+                     })
+                     .collect::<Result<Vec<_>>>()?;
+    Ok(Program { instrs: instrs.to_vec(), registers, code, superinstrs: Vec::new() })
+  }
+
+  pub fn instrs(&self) -> &[Instr] {
+    &self.instrs
+  }
+
+  pub fn registers(&self) -> &RegisterFile {
+    &self.registers
+  }
+
+  /// A 64-bit FNV-1a hash of the listing, in hex, to tell programs apart.
+  pub fn fingerprint(&self) -> String {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for instr in &self.instrs {
+      for b in instr.to_string().bytes().chain([b'\n']) {
+        hash = (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3);
+      }
+    }
+    format!("{:016x}", hash)
+  }
+
+  pub fn len(&self) -> usize {
+    self.code.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.code.is_empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::dialect::COPROCESSOR;
+
+  #[test]
+  fn test_jumps_resolved() {
+    let prog = Program::compile(&COPROCESSOR.parse("jnz 1 2\njnz a -1\njnz b c").unwrap())
+        .unwrap();
+    assert_eq!(prog.code, vec![Code::Jmp(2), Code::Jnz(Arg::Reg(0), Target::To(0)),
+                               Code::Jnz(Arg::Reg(1), Target::By(2))]);
+  }
+
+  #[test]
+  fn test_write_to_immediate() {
+    let mut instrs = COPROCESSOR.parse("set a 1").unwrap();
+    instrs.push(Instr::Sub(Val::Num(2), Val::Reg("a".to_owned())));
+    let err = Program::compile(&instrs).unwrap_err();
+    assert_eq!(err.to_string(), "line 2, col 1: Value not a register: 2 (in 'sub 2 a')");
+  }
+}  // mod tests
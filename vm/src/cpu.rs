@@ -0,0 +1,477 @@
+use aoc_common::{AocError, Result, invalid_input};
+use num_bigint::{BigInt, Sign};
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::arith::{Arith, BinOp, Word};
+use crate::bytecode::{Arg, Code, Program, Target};
+use crate::instr::{Instr, Op, jump_target};
+use crate::profile::Counters;
+use crate::snapshot::Snapshot;
+use crate::trace::{Event, State, Trace};
+
+
+/// What a single step did, for whoever is running the program to act on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Step {
+  Ran,
+  /// A `snd`, whose value the host delivers wherever it should go.
+  Sent(i64),
+  /// A sound `rcv` with a non-zero operand.
+  Recovered,
+  /// A `rcv` with nothing queued. The pc stays put so it's retried.
+  Blocked,
+  /// The pc is outside the program.
+  Halted,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Debug)]
+pub struct CPU<'a> {
+  prog: &'a Program,
+  pc: i64,
+  regs: Vec<i64>,
+  /// Registers too big for 64 bits, by slot. Their slot in `regs` holds
+  /// just their sign, which is all a jump's condition needs.
+  big: BTreeMap<usize, BigInt>,
+  arith: Arith,
+  inbox: VecDeque<i64>,
+  tx_count: usize,
+  counters: Counters,
+  trace: Option<Trace>,
+}
+
+fn unknown_reg(name: &str) -> AocError {
+  invalid_input!("Unknown register: {}", name)
+}
+
+fn too_big(name: &str) -> AocError {
+  invalid_input!("{} doesn't fit in 64 bits", name)
+}
+
+impl<'a> CPU<'a> {
+  /// A CPU with checked arithmetic and truncating `mod`.
+  pub fn new(prog: &'a Program) -> CPU<'a> {
+    CPU::with_arith(prog, Arith::default())
+  }
+
+  pub fn with_arith(prog: &'a Program, arith: Arith) -> CPU<'a> {
+    CPU {
+      prog,
+      pc: 0,
+      regs: vec![0; prog.registers().len()],
+      big: BTreeMap::new(),
+      arith,
+      inbox: VecDeque::new(),
+      tx_count: 0,
+      counters: Counters::new(prog.len(), prog.registers().len()),
+      trace: None,
+    }
+  }
+
+  pub fn program(&self) -> &'a Program {
+    self.prog
+  }
+
+  pub fn arith(&self) -> Arith {
+    self.arith
+  }
+
+  pub fn pc(&self) -> i64 {
+    self.pc
+  }
+
+  /// The instruction at the pc, unless the program has halted.
+  pub fn instr(&self) -> Option<&'a Instr> {
+    usize::try_from(self.pc).ok().and_then(|pc| self.prog.instrs().get(pc))
+  }
+
+  fn slot(&self, name: &str) -> Result<usize> {
+    self.prog.registers().slot(name).ok_or_else(|| unknown_reg(name))
+  }
+
+  /// Registers start out as zero. Only those in the program's register
+  /// file exist.
+  pub fn reg(&self, name: &str) -> Result<i64> {
+    self.exact(Arg::Reg(self.slot(name)?))
+  }
+
+  /// A register however big it's grown.
+  pub fn big_reg(&self, name: &str) -> Result<BigInt> {
+    Ok(self.load(Arg::Reg(self.slot(name)?)).into())
+  }
+
+  pub fn set_reg(&mut self, name: &str, val: i64) -> Result<()> {
+    let idx = self.slot(name)?;
+    self.store(idx, Word::Small(val));
+    Ok(())
+  }
+
+  /// Queues a message for a later `rcv`.
+  pub fn deliver(&mut self, n: i64) {
+    self.inbox.push_back(n);
+  }
+
+  /// Messages delivered but not yet received.
+  pub fn pending(&self) -> usize {
+    self.inbox.len()
+  }
+
+  /// How many values the program has sent.
+  pub fn tx_count(&self) -> usize {
+    self.tx_count
+  }
+
+  /// How many times an op has been executed.
+  pub fn count(&self, op: Op) -> usize {
+    self.prog.instrs()
+             .iter()
+             .zip(&self.counters.hits)
+             .filter(|(instr, _)| instr.op() == op)
+             .map(|(_, hits)| hits)
+             .sum()
+  }
+
+  pub(crate) fn counters(&self) -> &Counters {
+    &self.counters
+  }
+
+  /// Starts recording every step from here on, keeping only the last
+  /// `limit` if there is one.
+  pub fn record(&mut self, limit: Option<usize>) {
+    let (regs, big) = self.values();
+    self.trace = Some(Trace::new(State { step: 0, pc: self.pc, regs, big }, limit));
+  }
+
+  pub fn trace(&self) -> Option<&Trace> {
+    self.trace.as_ref()
+  }
+
+  /// Stops recording, handing over what was recorded.
+  pub fn take_trace(&mut self) -> Option<Trace> {
+    self.trace.take()
+  }
+
+  // Every register by name, those too big for 64 bits apart and in decimal.
+  fn values(&self) -> (BTreeMap<String, i64>, BTreeMap<String, String>) {
+    let mut regs = BTreeMap::new();
+    let mut big = BTreeMap::new();
+    for (idx, name) in self.prog.registers().names().iter().enumerate() {
+      match self.big.get(&idx) {
+        Some(n) => {
+          big.insert(name.clone(), n.to_string());
+        },
+        None => {
+          regs.insert(name.clone(), self.regs[idx]);
+        },
+      }
+    }
+    (regs, big)
+  }
+
+  /// Everything needed to carry on from here in another `CPU` running the
+  /// same program.
+  pub fn snapshot(&self) -> Snapshot {
+    let (regs, big) = self.values();
+    Snapshot {
+      program: self.prog.fingerprint(),
+      pc: self.pc,
+      regs,
+      big,
+      inbox: self.inbox.iter().copied().collect(),
+      tx_count: self.tx_count,
+      counters: self.counters.clone(),
+    }
+  }
+
+  /// Puts the CPU where the snapshot was taken, registers it leaves out
+  /// being zero. The snapshot has to be of the same program.
+  pub fn restore(&mut self, snapshot: &Snapshot) -> Result<()> {
+    let counters = &snapshot.counters;
+    if snapshot.program != self.prog.fingerprint() || counters.hits.len() != self.prog.len()
+       || counters.taken.len() != self.prog.len() || counters.writes.len() != self.regs.len() {
+      return Err(invalid_input!("The snapshot is of a different program"));
+    }
+    let mut words = vec![Word::Small(0); self.regs.len()];
+    for (name, &n) in &snapshot.regs {
+      words[self.slot(name)?] = Word::Small(n);
+    }
+    for (name, n) in &snapshot.big {
+      let big = n.parse::<BigInt>().map_err(|_| invalid_input!("Invalid number: {}", n))?;
+      words[self.slot(name)?] = Word::new(big);
+    }
+    self.big.clear();
+    for (idx, word) in words.into_iter().enumerate() {
+      self.store(idx, word);
+    }
+    self.pc = snapshot.pc;
+    self.inbox = snapshot.inbox.iter().copied().collect();
+    self.tx_count = snapshot.tx_count;
+    self.counters = counters.clone();
+    Ok(())
+  }
+
+  // Good enough for a condition, even for a big register.
+  fn get(&self, arg: Arg) -> i64 {
+    match arg {
+      Arg::Reg(idx) => self.regs[idx],
+      Arg::Imm(n) => n,
+    }
+  }
+
+  // The value itself, which has to fit in 64 bits.
+  fn exact(&self, arg: Arg) -> Result<i64> {
+    match arg {
+      Arg::Reg(idx) if self.big.contains_key(&idx) => {
+        Err(too_big(self.prog.registers().name(idx)))
+      },
+      _ => Ok(self.get(arg)),
+    }
+  }
+
+  fn load(&self, arg: Arg) -> Word {
+    match arg {
+      Arg::Reg(idx) => match self.big.get(&idx) {
+        Some(n) => Word::Big(n.clone()),
+        None => Word::Small(self.regs[idx]),
+      },
+      Arg::Imm(n) => Word::Small(n),
+    }
+  }
+
+  fn store(&mut self, x: usize, word: Word) {
+    match word {
+      Word::Small(n) => {
+        self.regs[x] = n;
+        if !self.big.is_empty() {
+          self.big.remove(&x);
+        }
+      },
+      Word::Big(n) => {
+        self.regs[x] = if n.sign() == Sign::Minus { -1 } else { 1 };
+        self.big.insert(x, n);
+      },
+    }
+  }
+
+  // x = x op y, under the CPU's arithmetic.
+  fn compute(&mut self, op: BinOp, x: usize, y: Arg) -> Result<()> {
+    let word = match (self.load(Arg::Reg(x)), self.load(y)) {
+      (Word::Small(x), Word::Small(y)) => self.arith.apply(op, x, y)?,
+      (x, y) => self.arith.apply_big(op, x.into(), &y.into())?,
+    };
+    self.store(x, word);
+    Ok(())
+  }
+
+  fn jump(&mut self, pc: usize, target: Target) -> Result<i64> {
+    self.counters.taken[pc] += 1;
+    Ok(match target {
+      Target::To(to) => to,
+      Target::By(idx) => {
+        let to = jump_target(pc, self.exact(Arg::Reg(idx))?);
+        *self.counters.dynamic.entry((pc, to)).or_insert(0) += 1;
+        to
+      },
+    })
+  }
+
+  // Pins an error to the instruction that caused it.
+  fn fault(&self, pc: usize, err: AocError) -> AocError {
+    err.at(pc + 1, 1, &self.prog.instrs()[pc].to_string())
+  }
+
+  /// Runs the instruction at the pc. Errors, such as an overflow, carry the
+  /// 1-based line of the instruction.
+  pub fn step(&mut self) -> Result<Step> {
+    if self.trace.is_none() {
+      return self.execute();
+    }
+    let (pc, regs, front) = (self.pc, self.regs.clone(), self.inbox.front().copied());
+    let (pending, big) = (self.inbox.len(), self.big.clone());
+    let step = self.execute()?;
+    if let Step::Blocked | Step::Halted = step {
+      return Ok(step);
+    }
+    // Nothing from here on can fail, as the step has already happened.
+    let (mut writes, mut big_writes) = (Vec::new(), Vec::new());
+    for (idx, &old) in regs.iter().enumerate() {
+      if self.regs[idx] == old && self.big.get(&idx) == big.get(&idx) {
+        continue;
+      }
+      let name = self.prog.registers().name(idx).to_owned();
+      match self.big.get(&idx) {
+        Some(n) => big_writes.push((name, n.to_string())),
+        None => writes.push((name, self.regs[idx])),
+      }
+    }
+    let trace = self.trace.as_mut().expect("Only traced CPUs get here");
+    trace.push(Event {
+      step: trace.end(),
+      pc: pc as usize,
+      instr: self.prog.instrs()[pc as usize].to_string(),
+      writes,
+      big_writes,
+      sent: if let Step::Sent(n) = step { Some(n) } else { None },
+      received: front.filter(|_| self.inbox.len() < pending),
+      next: self.pc,
+    });
+    Ok(step)
+  }
+
+  fn execute(&mut self) -> Result<Step> {
+    let pc = match usize::try_from(self.pc) {
+      Ok(pc) if pc < self.prog.len() => pc,
+      _ => return Ok(Step::Halted),
+    };
+    let prog = self.prog;
+    let mut code = prog.code[pc];
+    if let Code::Idiom(idx) = code {
+      let superinstr = &prog.superinstrs[idx];
+      // Superinstructions only know about 64 bits.
+      if self.big.is_empty() && superinstr.apply(&mut self.regs, &mut self.counters) {
+        self.pc = superinstr.end as i64;
+        return Ok(Step::Ran);
+      }
+      code = superinstr.orig;
+    }
+    let mut next = self.pc + 1;
+    let step = match code {
+      Code::Add(x, y) => {
+        self.compute(BinOp::Add, x, y).map_err(|err| self.fault(pc, err))?;
+        Step::Ran
+      },
+      Code::Mod(x, y) => {
+        self.compute(BinOp::Mod, x, y).map_err(|err| self.fault(pc, err))?;
+        Step::Ran
+      },
+      Code::Mul(x, y) => {
+        self.compute(BinOp::Mul, x, y).map_err(|err| self.fault(pc, err))?;
+        Step::Ran
+      },
+      Code::Set(x, y) => {
+        self.store(x, self.load(y));
+        Step::Ran
+      },
+      Code::Sub(x, y) => {
+        self.compute(BinOp::Sub, x, y).map_err(|err| self.fault(pc, err))?;
+        Step::Ran
+      },
+      Code::Jgz(x, target) => {
+        if self.get(x) > 0 {
+          next = self.jump(pc, target).map_err(|err| self.fault(pc, err))?;
+        }
+        Step::Ran
+      },
+      Code::Jnz(x, target) => {
+        if self.get(x) != 0 {
+          next = self.jump(pc, target).map_err(|err| self.fault(pc, err))?;
+        }
+        Step::Ran
+      },
+      Code::Jmp(to) => {
+        next = self.jump(pc, Target::To(to))?;
+        Step::Ran
+      },
+      Code::Rcv(x) => match self.inbox.pop_front() {
+        Some(n) => {
+          self.store(x, Word::Small(n));
+          Step::Ran
+        },
+        None => return Ok(Step::Blocked),
+      },
+      Code::Recover(x) => if self.get(x) != 0 { Step::Recovered } else { Step::Ran },
+      Code::Snd(x) => {
+        let n = self.exact(x).map_err(|err| self.fault(pc, err))?;
+        self.tx_count += 1;
+        Step::Sent(n)
+      },
+      Code::Idiom(_) => unreachable!("Superinstructions replace plain instructions"),
+    };
+    if let Code::Add(x, _) | Code::Mod(x, _) | Code::Mul(x, _) | Code::Set(x, _)
+           | Code::Sub(x, _) | Code::Rcv(x) = code {
+      self.counters.writes[x] += 1;
+    }
+    self.counters.hits[pc] += 1;
+    self.pc = next;
+    Ok(step)
+  }
+
+  /// Steps until something other than a plain `Step::Ran` happens.
+  pub fn run(&mut self) -> Result<Step> {
+    loop {
+      match self.step()? {
+        Step::Ran => {},
+        step => return Ok(step),
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::arith::Overflow;
+  use crate::dialect::{DUET, Dialect, SOUND};
+  use crate::instr::Val;
+
+  fn compile(dialect: &Dialect, source: &str) -> Program {
+    Program::compile(&dialect.parse(source).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn test_sound() {
+    let prog = compile(&SOUND, "set a 1\nadd a 2\nmul a a\nmod a 5\nsnd a\nset a 0\nrcv a\n\
+                                jgz a -1\nset a 1\njgz a -2");
+    let mut cpu = CPU::new(&prog);
+    assert_eq!(cpu.run().unwrap(), Step::Sent(4));
+    assert_eq!(cpu.run().unwrap(), Step::Recovered);
+    assert_eq!(cpu.reg("a").unwrap(), 1);
+  }
+
+  #[test]
+  fn test_blocked_until_delivered() {
+    let prog = compile(&DUET, "rcv a\nsnd a");
+    let mut cpu = CPU::new(&prog);
+    assert_eq!(cpu.step().unwrap(), Step::Blocked);
+    assert_eq!(cpu.pc(), 0);
+    cpu.deliver(7);
+    assert_eq!(cpu.run().unwrap(), Step::Sent(7));
+    assert_eq!(cpu.run().unwrap(), Step::Halted);
+    assert_eq!((cpu.tx_count(), cpu.count(Op::Rcv)), (1, 1));
+  }
+
+  #[test]
+  fn test_arithmetic_modes() {
+    let prog = compile(&DUET, "set a 9223372036854775807\nadd a 1\njgz a 2\nsnd 0\nadd a -2\n\
+                               snd a\nmod a 0");
+    let err = CPU::new(&prog).run().unwrap_err();
+    assert_eq!(err.to_string(), "line 2, col 1: Overflow: 9223372036854775807 + 1 doesn't fit \
+                                 in 64 bits (in 'add a 1')");
+    // Wrapped, a is negative and falls through to the `snd 0`.
+    let arith = |overflow| Arith { overflow, ..Arith::default() };
+    let mut cpu = CPU::with_arith(&prog, arith(Overflow::Wrapping));
+    assert_eq!(cpu.run().unwrap(), Step::Sent(0));
+    let mut cpu = CPU::with_arith(&prog, arith(Overflow::Big));
+    assert_eq!(cpu.step().unwrap(), Step::Ran);
+    assert_eq!(cpu.step().unwrap(), Step::Ran);
+    assert_eq!(cpu.reg("a").unwrap_err().to_string(), "a doesn't fit in 64 bits");
+    assert_eq!(cpu.big_reg("a").unwrap(), BigInt::from(i64::MAX) + 1);
+    assert_eq!(cpu.run().unwrap(), Step::Sent(i64::MAX - 1));
+    let err = cpu.run().unwrap_err();
+    assert_eq!(err.to_string(), "line 7, col 1: Modulo by zero (in 'mod a 0')");
+  }
+
+  #[test]
+  fn test_jump_by_register() {
+    let prog = compile(&DUET, "set a 2\njgz a a\nset b 1\nset c 1");
+    let mut cpu = CPU::new(&prog);
+    assert_eq!(cpu.run().unwrap(), Step::Halted);
+    assert_eq!((cpu.reg("b").unwrap(), cpu.reg("c").unwrap()), (0, 1));
+    let prog = compile(&DUET, "set a 9223372036854775807\njgz a a");
+    let mut cpu = CPU::new(&prog);
+    assert_eq!(cpu.run().unwrap(), Step::Halted);
+    let instrs = [Instr::Jgz(Val::Num(1), Val::Num(i64::MAX))];
+    let prog = Program::compile(&instrs).unwrap();
+    assert_eq!(CPU::new(&prog).run().unwrap(), Step::Halted);
+  }
+}  // mod tests
@@ -0,0 +1,354 @@
+//! A step debugger around the `CPU`, driven by one command per line.
+
+use aoc_common::{Result, invalid_input, to_i64};
+use std::collections::VecDeque;
+use std::io::{BufRead, Write};
+
+use crate::cpu::{CPU, Step};
+
+
+/// How many steps `rstep` can undo.
+const HISTORY: usize = 10_000;
+
+const HELP: &str = "\
+step [n]              run n instructions (s)
+rstep [n]             undo the last n steps (rs)
+continue              run until a breakpoint, watchpoint, block or halt (c)
+break <pc>            stop before running the instruction at pc (b)
+break <reg> <op> <n>  stop when the comparison becomes true; op is one of == != < <= > >=
+delete <n>            remove breakpoint n (d)
+watch <reg>           stop whenever the register changes (w)
+unwatch <reg>
+info                  list breakpoints and watchpoints (i)
+regs                  show the registers (r)
+set <reg> <n>         change a register
+deliver <n>           queue a message for rcv
+list [n]              show n instructions either side of the pc (l)
+quit                  (q)";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Cmp {
+  Eq,
+  Ne,
+  Lt,
+  Le,
+  Gt,
+  Ge,
+}
+
+impl Cmp {
+  fn parse(s: &str) -> Result<Cmp> {
+    Ok(match s {
+      "==" => Cmp::Eq,
+      "!=" => Cmp::Ne,
+      "<" => Cmp::Lt,
+      "<=" => Cmp::Le,
+      ">" => Cmp::Gt,
+      ">=" => Cmp::Ge,
+      _ => return Err(invalid_input!("Unknown comparison: {}", s)),
+    })
+  }
+
+  fn holds(self, x: i64, y: i64) -> bool {
+    match self {
+      Cmp::Eq => x == y,
+      Cmp::Ne => x != y,
+      Cmp::Lt => x < y,
+      Cmp::Le => x <= y,
+      Cmp::Gt => x > y,
+      Cmp::Ge => x >= y,
+    }
+  }
+
+  fn symbol(self) -> &'static str {
+    match self {
+      Cmp::Eq => "==",
+      Cmp::Ne => "!=",
+      Cmp::Lt => "<",
+      Cmp::Le => "<=",
+      Cmp::Gt => ">",
+      Cmp::Ge => ">=",
+    }
+  }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Breakpoint {
+  Pc(i64),
+  Reg(String, Cmp, i64),
+}
+
+#[derive(Debug)]
+pub struct Debugger<'a> {
+  cpu: CPU<'a>,
+  history: VecDeque<CPU<'a>>,
+  breakpoints: Vec<Breakpoint>,
+  watches: Vec<String>,
+}
+
+fn count(arg: Option<&&str>) -> Result<usize> {
+  arg.map_or(Ok(1), |n| n.parse::<usize>().map_err(|_| invalid_input!("Invalid count: {}", n)))
+}
+
+impl<'a> Debugger<'a> {
+  pub fn new(cpu: CPU<'a>) -> Debugger<'a> {
+    Debugger {
+      cpu,
+      history: VecDeque::new(),
+      breakpoints: Vec::new(),
+      watches: Vec::new(),
+    }
+  }
+
+  pub fn cpu(&self) -> &CPU<'a> {
+    &self.cpu
+  }
+
+  fn reg_name(&self, name: &str) -> Result<String> {
+    if self.cpu.program().registers().slot(name).is_some() {
+      Ok(name.to_owned())
+    } else {
+      Err(invalid_input!("Unknown register: {}", name))
+    }
+  }
+
+  fn registers(&self) -> Vec<String> {
+    self.cpu.program().registers().names().to_vec()
+  }
+
+  fn hit(&self, breakpoint: &Breakpoint) -> Result<bool> {
+    Ok(match *breakpoint {
+      Breakpoint::Pc(pc) => self.cpu.pc() == pc,
+      Breakpoint::Reg(ref name, cmp, n) => cmp.holds(self.cpu.reg(name)?, n),
+    })
+  }
+
+  fn show_pc<W>(&self, out: &mut W) -> Result<()>
+      where W: Write {
+    match self.cpu.instr() {
+      Some(instr) => writeln!(out, "{:>4}: {}", self.cpu.pc(), instr)?,
+      None => writeln!(out, "halted at {}", self.cpu.pc())?,
+    }
+    Ok(())
+  }
+
+  // Runs one instruction, reporting anything that should stop a continue.
+  fn step_one<W>(&mut self, out: &mut W) -> Result<bool>
+      where W: Write {
+    let before = self.watches
+                     .iter()
+                     .map(|name| self.cpu.reg(name))
+                     .collect::<Result<Vec<_>>>()?;
+    let held = self.breakpoints.iter().map(|b| self.hit(b)).collect::<Result<Vec<_>>>()?;
+    let saved = self.cpu.clone();
+    let step = self.cpu.step()?;
+    let mut stop = false;
+    match step {
+      Step::Ran => {},
+      Step::Sent(n) => writeln!(out, "sent {}", n)?,
+      Step::Recovered => {
+        writeln!(out, "recovered")?;
+        stop = true;
+      },
+      Step::Blocked | Step::Halted => {
+        let what = if step == Step::Blocked { "blocked on an empty queue" } else { "halted" };
+        writeln!(out, "{}", what)?;
+        return Ok(true);
+      },
+    }
+    if self.history.len() == HISTORY {
+      self.history.pop_front();
+    }
+    self.history.push_back(saved);
+    for (name, old) in self.watches.iter().zip(before) {
+      let new = self.cpu.reg(name)?;
+      if new != old {
+        writeln!(out, "{}: {} -> {}", name, old, new)?;
+        stop = true;
+      }
+    }
+    // A register condition only stops the run when it starts to hold.
+    for (idx, (breakpoint, held)) in self.breakpoints.iter().zip(held).enumerate() {
+      let edge = matches!(*breakpoint, Breakpoint::Pc(_)) || !held;
+      if edge && self.hit(breakpoint)? {
+        writeln!(out, "breakpoint {}", idx + 1)?;
+        stop = true;
+      }
+    }
+    Ok(stop)
+  }
+
+  fn list<W>(&self, out: &mut W, around: usize) -> Result<()>
+      where W: Write {
+    let instrs = self.cpu.program().instrs();
+    let pc = self.cpu.pc();
+    let lo = pc.saturating_sub(around as i64).max(0) as usize;
+    let hi = (pc.saturating_add(around as i64 + 1).max(0) as usize).min(instrs.len());
+    for (idx, instr) in instrs.iter().enumerate().take(hi).skip(lo) {
+      let mark = if idx as i64 == pc { "=>" } else { "  " };
+      writeln!(out, "{} {:>4}: {}", mark, idx, instr)?;
+    }
+    Ok(())
+  }
+
+  /// Runs one command, returning false once it's time to quit.
+  pub fn command<W>(&mut self, line: &str, out: &mut W) -> Result<bool>
+      where W: Write {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let (&command, args) = match words.split_first() {
+      Some(split) => split,
+      None => return Ok(true),
+    };
+    match (command, args) {
+      ("step" | "s", [] | [_]) => {
+        for _ in 0..count(args.first())? {
+          if self.step_one(out)? {
+            break;
+          }
+        }
+        self.show_pc(out)?;
+      },
+      ("rstep" | "rs", [] | [_]) => {
+        for _ in 0..count(args.first())? {
+          match self.history.pop_back() {
+            Some(cpu) => self.cpu = cpu,
+            None => {
+              writeln!(out, "no more history")?;
+              break;
+            },
+          }
+        }
+        self.show_pc(out)?;
+      },
+      ("continue" | "c", []) => {
+        while !self.step_one(out)? {}
+        self.show_pc(out)?;
+      },
+      ("break" | "b", [pc]) => {
+        self.breakpoints.push(Breakpoint::Pc(to_i64(pc)?));
+        writeln!(out, "breakpoint {}", self.breakpoints.len())?;
+      },
+      ("break" | "b", [reg, cmp, n]) => {
+        let breakpoint = Breakpoint::Reg(self.reg_name(reg)?, Cmp::parse(cmp)?, to_i64(n)?);
+        self.breakpoints.push(breakpoint);
+        writeln!(out, "breakpoint {}", self.breakpoints.len())?;
+      },
+      ("delete" | "d", [n]) => {
+        let idx = count(Some(n))?;
+        if idx == 0 || idx > self.breakpoints.len() {
+          return Err(invalid_input!("No breakpoint {}", n));
+        }
+        self.breakpoints.remove(idx - 1);
+      },
+      ("watch" | "w", [reg]) => {
+        let name = self.reg_name(reg)?;
+        if !self.watches.contains(&name) {
+          self.watches.push(name);
+        }
+      },
+      ("unwatch", [reg]) => self.watches.retain(|name| name != reg),
+      ("info" | "i", []) => {
+        for (idx, breakpoint) in self.breakpoints.iter().enumerate() {
+          match *breakpoint {
+            Breakpoint::Pc(pc) => writeln!(out, "breakpoint {}: pc {}", idx + 1, pc)?,
+            Breakpoint::Reg(ref name, cmp, n) => {
+              writeln!(out, "breakpoint {}: {} {} {}", idx + 1, name, cmp.symbol(), n)?
+            },
+          }
+        }
+        for name in &self.watches {
+          writeln!(out, "watch {}", name)?;
+        }
+      },
+      ("regs" | "r", []) => {
+        let regs = self.registers()
+                       .iter()
+                       .map(|name| Ok(format!("{}={}", name, self.cpu.big_reg(name)?)))
+                       .collect::<Result<Vec<_>>>()?;
+        writeln!(out, "pc={} {}", self.cpu.pc(), regs.join(" "))?;
+        if self.cpu.pending() > 0 {
+          writeln!(out, "{} messages queued", self.cpu.pending())?;
+        }
+      },
+      ("set", [reg, n]) => {
+        let name = self.reg_name(reg)?;
+        self.cpu.set_reg(&name, to_i64(n)?)?;
+      },
+      ("deliver", [n]) => self.cpu.deliver(to_i64(n)?),
+      ("list" | "l", [] | [_]) => {
+        let around = args.first().map_or(Ok(3), |n| count(Some(n)))?;
+        self.list(out, around)?;
+      },
+      ("help" | "h", []) => writeln!(out, "{}", HELP)?,
+      ("quit" | "q", []) => return Ok(false),
+      _ => return Err(invalid_input!("Unknown command: {} (try help)", line.trim())),
+    }
+    Ok(true)
+  }
+
+  /// Reads commands until `quit` or the end of the input, reporting errors
+  /// in a command without stopping.
+  pub fn repl<R, W>(&mut self, input: R, mut out: W) -> Result<()>
+      where R: BufRead, W: Write {
+    self.show_pc(&mut out)?;
+    write!(out, "(vm) ")?;
+    out.flush()?;
+    for line in input.lines() {
+      match self.command(&line?, &mut out) {
+        Ok(true) => {},
+        Ok(false) => break,
+        Err(err) => writeln!(out, "Error: {}", err)?,
+      }
+      write!(out, "(vm) ")?;
+      out.flush()?;
+    }
+    writeln!(out)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::dialect::COPROCESSOR;
+
+  const COUNTDOWN: &str = "set a 3\nsub a 1\njnz a -1\nset b 7";
+
+  fn session(commands: &str) -> String {
+    let prog = COPROCESSOR.compile(COUNTDOWN).unwrap();
+    let mut debugger = Debugger::new(CPU::new(&prog));
+    let mut out = Vec::new();
+    debugger.repl(commands.as_bytes(), &mut out).unwrap();
+    String::from_utf8(out).unwrap().replace("(vm) ", "")
+  }
+
+  #[test]
+  fn test_breakpoints_and_rstep() {
+    assert_eq!(session("break a == 1\nc\nrs 2\nregs\nb 3\nc\ndelete 9\n"), "   \
+   0: set a 3
+breakpoint 1
+breakpoint 1
+   2: jnz a -1
+   2: jnz a -1
+pc=2 a=2 b=0 c=0 d=0 e=0 f=0 g=0 h=0
+breakpoint 2
+breakpoint 1
+   2: jnz a -1
+Error: No breakpoint 9
+
+");
+  }
+
+  #[test]
+  fn test_watch_until_halt() {
+    assert_eq!(session("watch b\ncontinue\ns\nbogus"), "   \
+   0: set a 3
+b: 0 -> 7
+halted at 4
+halted
+halted at 4
+Error: Unknown command: bogus (try help)
+
+");
+  }
+}  // mod tests
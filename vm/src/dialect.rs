@@ -0,0 +1,177 @@
+use aoc_common::Result;
+use std::ops::RangeInclusive;
+
+use crate::bytecode::{Program, RegisterFile};
+use crate::instr::{Instr, Op};
+use crate::verify::combine;
+
+
+/// The names a dialect's registers can have.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Registers {
+  /// Single letters, like the puzzles' `a` to `z`.
+  Letters(RangeInclusive<char>),
+  /// Exactly these names.
+  Named(&'static [&'static str]),
+  /// Any identifier, with each program getting the registers it names.
+  Any,
+}
+
+pub(crate) fn is_ident(s: &str) -> bool {
+  let mut chars = s.chars();
+  chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl Registers {
+  pub fn contains(&self, name: &str) -> bool {
+    match *self {
+      Registers::Letters(ref letters) => {
+        let mut chars = name.chars();
+        match (chars.next(), chars.next()) {
+          (Some(c), None) => letters.contains(&c),
+          _ => false,
+        }
+      },
+      Registers::Named(names) => names.contains(&name),
+      Registers::Any => is_ident(name),
+    }
+  }
+
+  /// Every register, unless any name will do.
+  pub fn fixed(&self) -> Option<Vec<String>> {
+    match *self {
+      Registers::Letters(ref letters) => Some(letters.clone().map(String::from).collect()),
+      Registers::Named(names) => Some(names.iter().map(|&name| name.to_owned()).collect()),
+      Registers::Any => None,
+    }
+  }
+}
+
+/// One puzzle's instruction set: which ops its mnemonics mean and which
+/// registers it has.
+#[derive(Debug)]
+pub struct Dialect {
+  pub name: &'static str,
+  pub ops: &'static [Op],
+  pub registers: Registers,
+}
+
+/// Day 18 part 1, where `snd` plays a sound and `rcv` recovers it.
+pub static SOUND: Dialect = Dialect {
+  name: "sound",
+  ops: &[Op::Add, Op::Jgz, Op::Mod, Op::Mul, Op::Recover, Op::Set, Op::Snd],
+  registers: Registers::Letters('a'..='z'),
+};
+
+/// Day 18 part 2, where `snd` and `rcv` pass messages between programs.
+pub static DUET: Dialect = Dialect {
+  name: "duet",
+  ops: &[Op::Add, Op::Jgz, Op::Mod, Op::Mul, Op::Rcv, Op::Set, Op::Snd],
+  registers: Registers::Letters('a'..='z'),
+};
+
+/// Day 23's coprocessor.
+pub static COPROCESSOR: Dialect = Dialect {
+  name: "coprocessor",
+  ops: &[Op::Jnz, Op::Mul, Op::Set, Op::Sub],
+  registers: Registers::Letters('a'..='h'),
+};
+
+/// Every dialect, for looking one up by name.
+pub static DIALECTS: [&Dialect; 3] = [&SOUND, &DUET, &COPROCESSOR];
+
+impl Dialect {
+  pub fn named(name: &str) -> Option<&'static Dialect> {
+    DIALECTS.iter().copied().find(|dialect| dialect.name == name)
+  }
+
+  pub fn op(&self, mnemonic: &str) -> Option<Op> {
+    self.ops.iter().copied().find(|op| op.name() == mnemonic)
+  }
+
+  pub fn valid_reg(&self, name: &str) -> bool {
+    self.registers.contains(name)
+  }
+
+  /// Parses one line such as `jgz a -2`.
+  pub fn instr(&self, line: &str) -> Result<Instr> {
+    self.check(1, line).map_err(|mut problems| problems.remove(0))
+  }
+
+  /// Parses a whole program, one instruction per line, reporting every
+  /// problem `verify` finds.
+  pub fn parse(&self, data: &str) -> Result<Vec<Instr>> {
+    let (instrs, problems) = self.verify(data);
+    combine(instrs, problems)
+  }
+
+  /// The register file for a program: all of a fixed set, or just those
+  /// the program names.
+  pub fn register_file(&self, instrs: &[Instr]) -> RegisterFile {
+    match self.registers.fixed() {
+      Some(names) => RegisterFile::new(names),
+      None => RegisterFile::of(instrs),
+    }
+  }
+
+  /// Compiles parsed instructions for the `CPU`.
+  pub fn build(&self, instrs: &[Instr]) -> Result<Program> {
+    Program::with_registers(instrs, self.register_file(instrs))
+  }
+
+  /// Parses a program and compiles it for the `CPU`.
+  pub fn compile(&self, data: &str) -> Result<Program> {
+    self.build(&self.parse(data)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::cpu::{CPU, Step};
+  use crate::instr::Val;
+
+  #[test]
+  fn test_rcv_depends_on_dialect() {
+    assert_eq!(SOUND.instr("rcv a").unwrap(), Instr::Recover(Val::Reg("a".to_owned())));
+    assert_eq!(DUET.instr("rcv a").unwrap(), Instr::Rcv(Val::Reg("a".to_owned())));
+  }
+
+  static WIDE: Dialect = Dialect {
+    name: "wide",
+    ops: &[Op::Add, Op::Jgz, Op::Set, Op::Snd],
+    registers: Registers::Any,
+  };
+
+  static NAMED: Dialect = Dialect {
+    name: "named",
+    ops: &[Op::Add, Op::Set],
+    registers: Registers::Named(&["acc", "x"]),
+  };
+
+  #[test]
+  fn test_any_register_names() {
+    let source = "set count 3\nadd total_2 count\nadd count -1\njgz count -2";
+    let prog = WIDE.compile(source).unwrap();
+    assert_eq!(prog.registers().names(), ["count", "total_2"]);
+    let mut cpu = CPU::new(&prog);
+    assert_eq!(cpu.run().unwrap(), Step::Halted);
+    assert_eq!(cpu.reg("total_2").unwrap(), 6);
+    assert_eq!(cpu.reg("a").unwrap_err().to_string(), "Unknown register: a");
+  }
+
+  #[test]
+  fn test_named_registers() {
+    let prog = NAMED.compile("set acc 2\nadd acc x").unwrap();
+    assert_eq!(prog.registers().names(), ["acc", "x"]);
+    let err = NAMED.parse("set acc 1\nadd a x").unwrap_err();
+    assert_eq!(err.to_string(), "line 2, col 5: Unknown register: a (in 'add a x')");
+  }
+
+  #[test]
+  fn test_unknown_op_for_dialect() {
+    let err = COPROCESSOR.parse("set a 1\njgz a -1").unwrap_err();
+    assert_eq!(err.to_string(), "line 2, col 1: Unknown instruction: jgz (in 'jgz a -1')");
+  }
+}  // mod tests
@@ -0,0 +1,281 @@
+//! Turns a program back into something readable: jump offsets become labels,
+//! the code is split into basic blocks, and the loops between them are found
+//! from their back edges.
+
+use aoc_common::Result;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::instr::{Instr, Val, jump_target};
+
+
+/// Where control can go after a block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Succ {
+  Block(usize),
+  /// Off either end of the program, which halts it.
+  Exit,
+  /// A jump by a register, which could go anywhere.
+  Unknown,
+}
+
+/// A straight run of instructions from `start` up to `end`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Block {
+  pub start: usize,
+  pub end: usize,
+  pub succs: Vec<Succ>,
+}
+
+/// The blocks from `head` to `tail`, where `tail` jumps back to `head`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Loop {
+  pub head: usize,
+  pub tail: usize,
+  /// 1 for an outermost loop.
+  pub depth: usize,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Jump {
+  None,
+  /// Taken or not depending on a register.
+  Cond(i64),
+  Always(i64),
+  Dynamic,
+}
+
+fn jump(pc: usize, instr: &Instr) -> Jump {
+  let (cond, offset, taken): (_, _, fn(i64) -> bool) = match *instr {
+    Instr::Jgz(ref x, ref y) => (x, y, |n| n > 0),
+    Instr::Jnz(ref x, ref y) => (x, y, |n| n != 0),
+    _ => return Jump::None,
+  };
+  let to = match *offset {
+    Val::Num(n) => jump_target(pc, n),
+    _ => return Jump::Dynamic,
+  };
+  match *cond {
+    Val::Num(n) if taken(n) => Jump::Always(to),
+    Val::Num(_) => Jump::None,
+    _ => Jump::Cond(to),
+  }
+}
+
+#[derive(Debug)]
+pub struct Disassembly<'a> {
+  instrs: &'a [Instr],
+  jumps: Vec<Jump>,
+  pub blocks: Vec<Block>,
+  pub loops: Vec<Loop>,
+}
+
+impl<'a> Disassembly<'a> {
+  pub fn new(instrs: &'a [Instr]) -> Disassembly<'a> {
+    let len = instrs.len();
+    let jumps = instrs.iter().enumerate().map(|(pc, instr)| jump(pc, instr)).collect::<Vec<_>>();
+    let mut leaders = BTreeSet::new();
+    leaders.insert(0);
+    for (pc, jump) in jumps.iter().enumerate() {
+      if let Jump::Cond(to) | Jump::Always(to) = *jump {
+        if (0..len as i64).contains(&to) {
+          leaders.insert(to as usize);
+        }
+      }
+      if *jump != Jump::None {
+        leaders.insert(pc + 1);
+      }
+    }
+    let starts = leaders.into_iter().filter(|&pc| pc < len).collect::<Vec<_>>();
+    let block_at = |pc: i64| match starts.binary_search(&(pc as usize)) {
+      Ok(idx) if pc >= 0 => Succ::Block(idx),
+      _ => Succ::Exit,
+    };
+    let blocks = starts.iter()
+                       .enumerate()
+                       .map(|(idx, &start)| {
+                         let end = starts.get(idx + 1).copied().unwrap_or(len);
+                         let next = block_at(end as i64);
+                         let succs = match jumps[end - 1] {
+                           Jump::None => vec![next],
+                           Jump::Cond(to) => vec![next, block_at(to)],
+                           Jump::Always(to) => vec![block_at(to)],
+                           Jump::Dynamic => vec![next, Succ::Unknown],
+                         };
+                         Block { start, end, succs }
+                       })
+                       .collect::<Vec<_>>();
+    let mut loops = Vec::new();
+    for (tail, block) in blocks.iter().enumerate() {
+      for succ in &block.succs {
+        if let Succ::Block(head) = *succ {
+          if head <= tail {
+            loops.push(Loop { head, tail, depth: 1 });
+          }
+        }
+      }
+    }
+    for idx in 0..loops.len() {
+      let Loop { head, tail, .. } = loops[idx];
+      loops[idx].depth = 1 + loops.iter()
+          .filter(|l| l.head <= head && tail <= l.tail && (l.head, l.tail) != (head, tail))
+          .count();
+    }
+    loops.sort_by_key(|l| (l.head, l.tail));
+    Disassembly { instrs, jumps, blocks, loops }
+  }
+
+  /// The label for a jump target. Every target off the program halts it.
+  pub fn label(&self, pc: i64) -> String {
+    if (0..self.instrs.len() as i64).contains(&pc) {
+      format!("L{}", pc)
+    } else {
+      "halt".to_owned()
+    }
+  }
+
+  /// Where the jump at pc goes, if it's a jump to a constant offset.
+  pub(crate) fn target(&self, pc: usize) -> Option<i64> {
+    match self.jumps[pc] {
+      Jump::Cond(to) | Jump::Always(to) => Some(to),
+      _ => None,
+    }
+  }
+
+  /// Whether the instruction at pc is a jump that depends on a register.
+  pub(crate) fn is_dynamic(&self, pc: usize) -> bool {
+    self.jumps[pc] == Jump::Dynamic
+  }
+
+  fn is_target(&self, pc: usize) -> bool {
+    (0..self.instrs.len()).any(|from| self.target(from) == Some(pc as i64))
+  }
+
+  /// The program with a label on every jump target and the target after every
+  /// jump, one block per paragraph and each loop introduced by a comment.
+  pub fn listing(&self) -> Result<String> {
+    let mut out = String::new();
+    for (idx, block) in self.blocks.iter().enumerate() {
+      if idx > 0 {
+        writeln!(out)?;
+      }
+      for l in self.loops.iter().filter(|l| l.head == idx) {
+        let last = self.blocks[l.tail].end - 1;
+        writeln!(out, "; loop {}..L{}, depth {}", self.label(block.start as i64), last, l.depth)?;
+      }
+      for pc in block.start..block.end {
+        let label = if self.is_target(pc) {
+          format!("{}:", self.label(pc as i64))
+        } else {
+          String::new()
+        };
+        let instr = self.instrs[pc].to_string();
+        match self.target(pc) {
+          Some(to) => writeln!(out, "{:>8} {:<12} -> {}", label, instr, self.label(to))?,
+          None => writeln!(out, "{:>8} {}", label, instr)?,
+        }
+      }
+    }
+    if self.is_target(self.instrs.len()) {
+      writeln!(out, "{:>8}", "halt:")?;
+    }
+    Ok(out)
+  }
+
+  /// The control-flow graph in Graphviz's DOT language. Taken jumps are
+  /// labelled and back edges drawn bold.
+  pub fn dot(&self) -> Result<String> {
+    let mut out = String::new();
+    writeln!(out, "digraph program {{")?;
+    writeln!(out, "  node [shape=box, fontname=\"monospace\"];")?;
+    for (idx, block) in self.blocks.iter().enumerate() {
+      let mut label = format!("{}:\\l", self.label(block.start as i64));
+      for instr in &self.instrs[block.start..block.end] {
+        write!(label, "{}\\l", instr)?;
+      }
+      writeln!(out, "  b{} [label=\"{}\"];", idx, label)?;
+    }
+    writeln!(out, "  exit [shape=oval, label=\"halt\"];")?;
+    for (idx, block) in self.blocks.iter().enumerate() {
+      let jump = self.jumps[block.end - 1];
+      for (n, succ) in block.succs.iter().enumerate() {
+        let to = match *succ {
+          Succ::Block(to) => format!("b{}", to),
+          Succ::Exit => "exit".to_owned(),
+          Succ::Unknown => continue,
+        };
+        let mut attrs = Vec::new();
+        if n == 1 || matches!(jump, Jump::Always(_)) {
+          attrs.push("label=\"jump\"");
+        }
+        if matches!(*succ, Succ::Block(to) if to <= idx) {
+          attrs.push("style=bold");
+        }
+        if attrs.is_empty() {
+          writeln!(out, "  b{} -> {};", idx, to)?;
+        } else {
+          writeln!(out, "  b{} -> {} [{}];", idx, to, attrs.join(", "))?;
+        }
+      }
+    }
+    writeln!(out, "}}")?;
+    Ok(out)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::dialect::COPROCESSOR;
+
+  const NESTED: &str = "\
+set a 3
+set b 2
+sub b 1
+jnz b -1
+sub a 1
+jnz a -4
+jnz 1 2
+set c 1";
+
+  #[test]
+  fn test_blocks_and_loops() {
+    let instrs = COPROCESSOR.parse(NESTED).unwrap();
+    let dis = Disassembly::new(&instrs);
+    let ranges = dis.blocks.iter().map(|b| (b.start, b.end)).collect::<Vec<_>>();
+    assert_eq!(ranges, vec![(0, 1), (1, 2), (2, 4), (4, 6), (6, 7), (7, 8)]);
+    assert_eq!(dis.blocks[4].succs, vec![Succ::Exit]);
+    assert_eq!(dis.loops, vec![Loop { head: 1, tail: 3, depth: 1 },
+                               Loop { head: 2, tail: 2, depth: 2 }]);
+  }
+
+  #[test]
+  fn test_listing() {
+    let instrs = COPROCESSOR.parse(NESTED).unwrap();
+    assert_eq!(Disassembly::new(&instrs).listing().unwrap(), "         set a 3
+
+; loop L1..L5, depth 1
+     L1: set b 2
+
+; loop L2..L3, depth 2
+     L2: sub b 1
+         jnz b -1     -> L2
+
+         sub a 1
+         jnz a -4     -> L1
+
+         jnz 1 2      -> halt
+
+         set c 1
+   halt:
+");
+  }
+
+  #[test]
+  fn test_far_jumps_halt() {
+    let instrs = [Instr::Jnz(Val::Reg("a".to_owned()), Val::Num(i64::MAX))];
+    let dis = Disassembly::new(&instrs);
+    assert_eq!(dis.blocks[0].succs, vec![Succ::Exit, Succ::Exit]);
+    assert!(dis.listing().unwrap().contains("-> halt"));
+  }
+}  // mod tests
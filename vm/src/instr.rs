@@ -0,0 +1,126 @@
+use std::fmt;
+
+
+/// An operand: a register, an immediate or, when the text was neither, nothing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Val {
+  Reg(String),
+  Num(i64),
+  None,
+}
+
+impl fmt::Display for Val {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Val::Reg(ref name) => write!(f, "{}", name),
+      Val::Num(n) => write!(f, "{}", n),
+      Val::None => write!(f, "?"),
+    }
+  }
+}
+
+/// Every operation any dialect has, independent of its operands.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Op {
+  Add,
+  Jgz,
+  Jnz,
+  Mod,
+  Mul,
+  /// Receives a message into a register, waiting for one if none is queued.
+  Rcv,
+  /// Recovers the last sound played when its operand isn't zero.
+  Recover,
+  Set,
+  Snd,
+  Sub,
+}
+
+impl Op {
+  /// The mnemonic the puzzles write it as. Both kinds of receive are `rcv`.
+  pub fn name(self) -> &'static str {
+    match self {
+      Op::Add => "add",
+      Op::Jgz => "jgz",
+      Op::Jnz => "jnz",
+      Op::Mod => "mod",
+      Op::Mul => "mul",
+      Op::Rcv | Op::Recover => "rcv",
+      Op::Set => "set",
+      Op::Snd => "snd",
+      Op::Sub => "sub",
+    }
+  }
+
+  /// Builds the instruction from its operands; unary ops ignore `y`.
+  pub fn instr(self, x: Val, y: Val) -> Instr {
+    match self {
+      Op::Add => Instr::Add(x, y),
+      Op::Jgz => Instr::Jgz(x, y),
+      Op::Jnz => Instr::Jnz(x, y),
+      Op::Mod => Instr::Mod(x, y),
+      Op::Mul => Instr::Mul(x, y),
+      Op::Rcv => Instr::Rcv(x),
+      Op::Recover => Instr::Recover(x),
+      Op::Set => Instr::Set(x, y),
+      Op::Snd => Instr::Snd(x),
+      Op::Sub => Instr::Sub(x, y),
+    }
+  }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Instr {
+  Add(Val, Val),
+  Jgz(Val, Val),
+  Jnz(Val, Val),
+  Mod(Val, Val),
+  Mul(Val, Val),
+  Rcv(Val),
+  Recover(Val),
+  Set(Val, Val),
+  Snd(Val),
+  Sub(Val, Val),
+}
+
+impl Instr {
+  pub fn op(&self) -> Op {
+    match *self {
+      Instr::Add(..) => Op::Add,
+      Instr::Jgz(..) => Op::Jgz,
+      Instr::Jnz(..) => Op::Jnz,
+      Instr::Mod(..) => Op::Mod,
+      Instr::Mul(..) => Op::Mul,
+      Instr::Rcv(..) => Op::Rcv,
+      Instr::Recover(..) => Op::Recover,
+      Instr::Set(..) => Op::Set,
+      Instr::Snd(..) => Op::Snd,
+      Instr::Sub(..) => Op::Sub,
+    }
+  }
+
+  pub fn operands(&self) -> Vec<&Val> {
+    match *self {
+      Instr::Add(ref x, ref y) | Instr::Jgz(ref x, ref y) | Instr::Jnz(ref x, ref y)
+        | Instr::Mod(ref x, ref y) | Instr::Mul(ref x, ref y) | Instr::Set(ref x, ref y)
+        | Instr::Sub(ref x, ref y) => vec![x, y],
+      Instr::Rcv(ref x) | Instr::Recover(ref x) | Instr::Snd(ref x) => vec![x],
+    }
+  }
+}
+
+/// Where a jump by `offset` from `pc` goes. One too far to add up is off the
+/// end of the program all the same, so it halts.
+pub(crate) fn jump_target(pc: usize, offset: i64) -> i64 {
+  (pc as i64).checked_add(offset).unwrap_or(i64::MAX)
+}
+
+impl fmt::Display for Instr {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.op().name())?;
+    for val in self.operands() {
+      write!(f, " {}", val)?;
+    }
+    Ok(())
+  }
+}
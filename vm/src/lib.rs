@@ -0,0 +1,42 @@
+//! The register machine behind the 2017 duet (day 18) and coprocessor (day 23)
+//! puzzles. Each puzzle is a `Dialect` of the same instruction set, run by the
+//! same `CPU`; what a send or a receive means is left to whoever runs it.
+//! Programs are compiled to a `Program` first, with registers resolved to
+//! slots in its `RegisterFile` and constant jumps to their targets, and
+//! can then have the loops it recognizes replaced with native code. A `CPU`
+//! does its arithmetic as its `Arith` says and counts what it runs, which a
+//! `Profile` reports on, and a `Scheduler` runs several of them that talk to
+//! each other, in turn or on threads of their own.
+//! A `CPU` can record a `Trace` of every step to replay or compare later, or
+//! save a `Snapshot` to carry on from, and an `Analysis` works out what the
+//! loops do without running anything.
+
+mod analyze;
+mod arith;
+mod asm;
+mod bytecode;
+mod cpu;
+mod debug;
+mod dialect;
+mod disasm;
+mod instr;
+mod optimize;
+mod profile;
+mod sched;
+mod snapshot;
+mod threads;
+mod trace;
+mod verify;
+
+pub use analyze::{Affine, Analysis, Induction, LoopSummary};
+pub use arith::{Arith, Modulo, Overflow};
+pub use bytecode::{Program, RegisterFile};
+pub use cpu::{CPU, Step};
+pub use debug::Debugger;
+pub use dialect::{COPROCESSOR, DIALECTS, DUET, Dialect, Registers, SOUND};
+pub use disasm::{Block, Disassembly, Loop, Succ};
+pub use instr::{Instr, Op, Val};
+pub use profile::{Edge, Line, LoopHeat, Profile};
+pub use sched::{Policy, Proc, Report, Scheduler, Status, Topology};
+pub use snapshot::Snapshot;
+pub use trace::{Diff, Event, State, Trace};
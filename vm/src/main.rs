@@ -0,0 +1,257 @@
+use aoc_common::{Result, invalid_input, read_input, to_i64};
+use aoc_vm::{Analysis, Arith, CPU, DIALECTS, Debugger, Dialect, Disassembly, Instr, Modulo,
+             Overflow, Profile, Snapshot, Step, Trace};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+
+const USAGE: &str = "Usage: vm analyze <dialect> <path|-> [--set <reg>=<n>]...
+       vm asm <dialect> <path|->
+       vm check <dialect> <path|->
+       vm disasm <dialect> <path|-> [--dot]
+       vm debug <dialect> <path> [<arith>]
+       vm profile <dialect> <path|-> [--set <reg>=<n>]... [--optimize] [--json] [<arith>]
+       vm trace <dialect> <path|-> <out> [--set <reg>=<n>]... [--limit <n>] [--steps <n>]
+                [--json] [<arith>]
+       vm run <dialect> <path|-> [--set <reg>=<n>]... [--steps <n>] [--resume <snapshot>]
+              [--save <snapshot>] [<arith>]
+       vm replay <trace> <step>
+       vm diff <trace> <trace>
+
+where <arith> is [--overflow checked|wrapping|big] [--mod truncating|euclidean]";
+
+#[derive(Debug)]
+enum Command {
+  Analyze { dialect: &'static Dialect, path: PathBuf, regs: Vec<(String, i64)> },
+  Asm { dialect: &'static Dialect, path: PathBuf },
+  Check { dialect: &'static Dialect, path: PathBuf },
+  Disasm { dialect: &'static Dialect, path: PathBuf, dot: bool },
+  Debug { dialect: &'static Dialect, path: PathBuf, arith: Arith },
+  Profile { dialect: &'static Dialect, path: PathBuf, regs: Vec<(String, i64)>, optimize: bool,
+            json: bool, arith: Arith },
+  Trace { dialect: &'static Dialect, path: PathBuf, out: PathBuf, regs: Vec<(String, i64)>,
+          limit: Option<usize>, steps: Option<usize>, json: bool, arith: Arith },
+  Run { dialect: &'static Dialect, path: PathBuf, regs: Vec<(String, i64)>,
+        steps: Option<usize>, resume: Option<PathBuf>, save: Option<PathBuf>, arith: Arith },
+  Replay { path: PathBuf, step: usize },
+  Diff { left: PathBuf, right: PathBuf },
+}
+
+fn parse_dialect(name: &str) -> Result<&'static Dialect> {
+  Dialect::named(name).ok_or_else(|| {
+    let names = DIALECTS.iter().map(|d| d.name).collect::<Vec<_>>();
+    invalid_input!("Unknown dialect: {} (expected one of {})", name, names.join(", "))
+  })
+}
+
+fn parse_count(n: &str) -> Result<usize> {
+  n.parse::<usize>().map_err(|_| invalid_input!("Invalid count: {}", n))
+}
+
+fn parse_args<I>(args: I) -> Result<Command>
+    where I: IntoIterator<Item = String> {
+  let mut positional = Vec::new();
+  let mut dot = false;
+  let mut regs = Vec::new();
+  let (mut optimize, mut json) = (false, false);
+  let (mut arith, mut arith_set) = (Arith::default(), false);
+  let (mut limit, mut steps) = (None, None);
+  let (mut resume, mut save) = (None, None);
+  let mut args = args.into_iter();
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--dot" => dot = true,
+      "--optimize" => optimize = true,
+      "--json" => json = true,
+      "--overflow" => {
+        arith.overflow = Overflow::parse(&args.next().unwrap_or_default())?;
+        arith_set = true;
+      },
+      "--mod" => {
+        arith.modulo = Modulo::parse(&args.next().unwrap_or_default())?;
+        arith_set = true;
+      },
+      "--limit" => limit = Some(parse_count(&args.next().unwrap_or_default())?),
+      "--steps" => steps = Some(parse_count(&args.next().unwrap_or_default())?),
+      "--resume" => resume = Some(PathBuf::from(args.next().unwrap_or_default())),
+      "--save" => save = Some(PathBuf::from(args.next().unwrap_or_default())),
+      "--set" => {
+        let set = args.next().unwrap_or_default();
+        let (name, n) = set.split_once('=')
+                           .ok_or_else(|| invalid_input!("Expected <reg>=<n>, got {:?}", set))?;
+        regs.push((name.to_owned(), to_i64(n)?));
+      },
+      "--help" | "-h" => return Err(invalid_input!("{}", USAGE)),
+      _ => positional.push(arg),
+    }
+  }
+  let snapshots = resume.is_some() || save.is_some();
+  let flags = dot as usize + !regs.is_empty() as usize + optimize as usize + json as usize
+      + arith_set as usize + limit.is_some() as usize + steps.is_some() as usize
+      + resume.is_some() as usize + save.is_some() as usize;
+  match positional.as_slice() {
+    [command, dialect, path] if command == "analyze" && flags == !regs.is_empty() as usize => {
+      Ok(Command::Analyze { dialect: parse_dialect(dialect)?, path: PathBuf::from(path), regs })
+    },
+    [command, dialect, path] if command == "asm" && flags == 0 => {
+      Ok(Command::Asm { dialect: parse_dialect(dialect)?, path: PathBuf::from(path) })
+    },
+    [command, dialect, path] if command == "check" && flags == 0 => {
+      Ok(Command::Check { dialect: parse_dialect(dialect)?, path: PathBuf::from(path) })
+    },
+    [command, dialect, path] if command == "disasm" && flags == dot as usize => {
+      Ok(Command::Disasm { dialect: parse_dialect(dialect)?, path: PathBuf::from(path), dot })
+    },
+    // The debugger reads its commands from stdin, so the program can't.
+    [command, dialect, path] if command == "debug" && flags == arith_set as usize
+                                && path != "-" => {
+      Ok(Command::Debug { dialect: parse_dialect(dialect)?, path: PathBuf::from(path), arith })
+    },
+    [command, dialect, path] if command == "profile" && !dot && limit.or(steps).is_none()
+                                && !snapshots => {
+      let (dialect, path) = (parse_dialect(dialect)?, PathBuf::from(path));
+      Ok(Command::Profile { dialect, path, regs, optimize, json, arith })
+    },
+    [command, dialect, path, out] if command == "trace" && !dot && !optimize && !snapshots => {
+      let (dialect, path, out) = (parse_dialect(dialect)?, PathBuf::from(path), PathBuf::from(out));
+      Ok(Command::Trace { dialect, path, out, regs, limit, steps, json, arith })
+    },
+    [command, dialect, path] if command == "run" && !dot && !optimize && !json
+                                && limit.is_none() => {
+      let (dialect, path) = (parse_dialect(dialect)?, PathBuf::from(path));
+      Ok(Command::Run { dialect, path, regs, steps, resume, save, arith })
+    },
+    [command, path, step] if command == "replay" && flags == 0 => {
+      Ok(Command::Replay { path: PathBuf::from(path), step: parse_count(step)? })
+    },
+    [command, left, right] if command == "diff" && flags == 0 => {
+      Ok(Command::Diff { left: PathBuf::from(left), right: PathBuf::from(right) })
+    },
+    _ => Err(invalid_input!("{}", USAGE)),
+  }
+}
+
+// Every command takes assembly, which plain programs already are.
+fn load(dialect: &Dialect, path: PathBuf) -> Result<Vec<Instr>> {
+  dialect.assemble(&read_input(path)?)
+}
+
+fn run(command: Command) -> Result<()> {
+  match command {
+    Command::Analyze { dialect, path, regs } => {
+      print!("{}", Analysis::new(&load(dialect, path)?, &regs)?);
+    },
+    Command::Asm { dialect, path } => {
+      for instr in load(dialect, path)? {
+        println!("{}", instr);
+      }
+    },
+    Command::Check { dialect, path } => {
+      load(dialect, path)?;
+      println!("No problems found");
+    },
+    Command::Disasm { dialect, path, dot } => {
+      let instrs = load(dialect, path)?;
+      let dis = Disassembly::new(&instrs);
+      print!("{}", if dot { dis.dot()? } else { dis.listing()? });
+    },
+    Command::Debug { dialect, path, arith } => {
+      let program = dialect.build(&load(dialect, path)?)?;
+      let mut debugger = Debugger::new(CPU::with_arith(&program, arith));
+      debugger.repl(io::stdin().lock(), io::stdout())?;
+    },
+    Command::Profile { dialect, path, regs, optimize, json, arith } => {
+      let mut program = dialect.build(&load(dialect, path)?)?;
+      if optimize {
+        program.optimize();
+      }
+      let mut cpu = CPU::with_arith(&program, arith);
+      for (name, n) in regs {
+        cpu.set_reg(&name, n)?;
+      }
+      // Sends go nowhere, so the profile covers the program up to its first
+      // block, recovery or halt.
+      while let Step::Ran | Step::Sent(_) = cpu.step()? {}
+      let profile = Profile::new(&cpu);
+      if json {
+        println!("{}", profile.json()?);
+      } else {
+        print!("{}", profile.report()?);
+      }
+    },
+    Command::Trace { dialect, path, out, regs, limit, steps, json, arith } => {
+      let program = dialect.build(&load(dialect, path)?)?;
+      let mut cpu = CPU::with_arith(&program, arith);
+      for (name, n) in regs {
+        cpu.set_reg(&name, n)?;
+      }
+      cpu.record(limit);
+      // As for a profile, though it can be cut short for programs that
+      // would run for ever.
+      let mut n = 0;
+      while steps.is_none_or(|steps| n < steps) {
+        match cpu.step()? {
+          Step::Ran | Step::Sent(_) => n += 1,
+          _ => break,
+        }
+      }
+      let trace = cpu.take_trace().expect("Recording started above");
+      let data = if json { trace.to_jsonl()?.into_bytes() } else { trace.to_bytes()? };
+      fs::write(&out, data)?;
+      println!("Recorded steps {} to {}", trace.start.step, trace.end());
+    },
+    Command::Run { dialect, path, regs, steps, resume, save, arith } => {
+      let program = dialect.build(&load(dialect, path)?)?;
+      let mut cpu = CPU::with_arith(&program, arith);
+      if let Some(resume) = resume {
+        cpu.restore(&Snapshot::from_json(&fs::read_to_string(resume)?)?)?;
+      }
+      for (name, n) in regs {
+        cpu.set_reg(&name, n)?;
+      }
+      // Stops after that many steps, or sooner at a block, recovery or halt.
+      let (mut n, mut step) = (0, Step::Ran);
+      while steps.is_none_or(|steps| n < steps) {
+        step = cpu.step()?;
+        match step {
+          Step::Ran | Step::Sent(_) => n += 1,
+          _ => break,
+        }
+      }
+      let regs = program.registers()
+                        .names()
+                        .iter()
+                        .map(|name| Ok(format!("{}={}", name, cpu.big_reg(name)?)))
+                        .collect::<Result<Vec<_>>>()?;
+      println!("{:?} at pc {}: {}", step, cpu.pc(), regs.join(" "));
+      if let Some(save) = save {
+        fs::write(save, cpu.snapshot().json()?)?;
+      }
+    },
+    Command::Replay { path, step } => {
+      let trace = Trace::load(&fs::read(path)?)?;
+      println!("{}", trace.state_at(step)?);
+      if let Some(event) = trace.event(step) {
+        println!("{}", event);
+      }
+    },
+    Command::Diff { left, right } => {
+      let diff = Trace::load(&fs::read(left)?)?.diff(&Trace::load(&fs::read(right)?)?)?;
+      if diff.is_empty() {
+        println!("No differences");
+      } else {
+        print!("{}", diff);
+      }
+    },
+  }
+  Ok(())
+}
+
+fn main() {
+  if let Err(err) = parse_args(env::args().skip(1)).and_then(run) {
+    eprintln!("Error: {}", err);
+    process::exit(1);
+  }
+}
@@ -0,0 +1,283 @@
+//! Replaces loops the coprocessor spends all its time in with native code.
+//! Each recognized loop becomes a superinstruction at its first pc. It checks
+//! the registers first, and when they're outside what it can summarize
+//! exactly it runs the original instruction instead. The counters for every
+//! instruction it skips are worked out, so profiles are unchanged.
+
+use crate::bytecode::{Arg, Code, Program, Target};
+use crate::profile::Counters;
+
+
+// Large enough for the coprocessor's numbers; small enough that no product
+// the original loops form can overflow.
+const LIMIT: i64 = 1 << 31;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Idiom {
+  /// `do { if d * e == b { f = 0 } e += 1 } while e != b`, using g as scratch.
+  Divides { b: usize, d: usize, e: usize, f: usize, g: usize },
+  /// The `Divides` loop run for every d up to b with e starting at 2, which
+  /// clears f when b has a factor of at least d.
+  IsComposite { b: usize, d: usize, e: usize, f: usize, g: usize },
+}
+
+/// A loop from `start` up to `end`, run as one instruction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Superinstr {
+  pub idiom: Idiom,
+  pub start: usize,
+  pub end: usize,
+  /// The instruction it replaced, run when the loop can't be summarized.
+  pub orig: Code,
+}
+
+fn distinct(regs: &[usize]) -> bool {
+  regs.iter().enumerate().all(|(idx, r)| !regs[idx + 1..].contains(r))
+}
+
+//   set g d
+//   mul g e
+//   sub g b
+//   jnz g 2
+//   set f 0
+//   sub e -1
+//   set g e
+//   sub g b
+//   jnz g -8
+fn divides_at(code: &[Code], p: usize) -> Option<Idiom> {
+  use self::Arg::{Imm, Reg};
+  let (g, d) = match *code.get(p)? {
+    Code::Set(g, Reg(d)) => (g, d),
+    _ => return None,
+  };
+  let (e, b, f) = match *code.get(p + 1..p + 9)? {
+    [Code::Mul(g1, Reg(e)), Code::Sub(g2, Reg(b)), Code::Jnz(Reg(g3), Target::To(skip)),
+     Code::Set(f, Imm(0)), Code::Sub(e1, Imm(-1)), Code::Set(g4, Reg(e2)),
+     Code::Sub(g5, Reg(b1)), Code::Jnz(Reg(g6), Target::To(back))]
+        if [g1, g2, g3, g4, g5, g6].iter().all(|&r| r == g) && e1 == e && e2 == e && b1 == b
+          && skip == p as i64 + 5 && back == p as i64 => (e, b, f),
+    _ => return None,
+  };
+  if !distinct(&[b, d, e, f, g]) {
+    return None;
+  }
+  Some(Idiom::Divides { b, d, e, f, g })
+}
+
+//   set e 2
+//   <divides loop>
+//   sub d -1
+//   set g d
+//   sub g b
+//   jnz g -13
+fn is_composite_at(code: &[Code], q: usize) -> Option<Idiom> {
+  use self::Arg::{Imm, Reg};
+  let (b, d, e, f, g) = match divides_at(code, q + 1)? {
+    Idiom::Divides { b, d, e, f, g } => (b, d, e, f, g),
+    _ => return None,
+  };
+  if *code.get(q)? != Code::Set(e, Imm(2)) {
+    return None;
+  }
+  match *code.get(q + 10..q + 14)? {
+    [Code::Sub(d1, Imm(-1)), Code::Set(g1, Reg(d2)), Code::Sub(g2, Reg(b1)),
+     Code::Jnz(Reg(g3), Target::To(back))]
+        if d1 == d && d2 == d && b1 == b && [g1, g2, g3].iter().all(|&r| r == g)
+          && back == q as i64 => Some(Idiom::IsComposite { b, d, e, f, g }),
+    _ => None,
+  }
+}
+
+// How many divisors of `n` lie in `lo..=hi`.
+fn divisors_between(n: i64, lo: i64, hi: i64) -> usize {
+  let mut count = 0;
+  let mut k = 1;
+  while k * k <= n {
+    if n % k == 0 {
+      let pair = n / k;
+      count += (lo..=hi).contains(&k) as usize;
+      if pair != k {
+        count += (lo..=hi).contains(&pair) as usize;
+      }
+    }
+    k += 1;
+  }
+  count
+}
+
+fn add_hits(hits: &mut [usize], pcs: impl Iterator<Item = usize>, n: usize) {
+  for pc in pcs {
+    hits[pc] += n;
+  }
+}
+
+impl Superinstr {
+  /// Runs the whole loop if the registers allow it, returning whether it did.
+  pub fn apply(&self, regs: &mut [i64], counters: &mut Counters) -> bool {
+    let s = self.start;
+    let Counters { hits, writes, taken, .. } = counters;
+    match self.idiom {
+      Idiom::Divides { b, d, e, f, g } => {
+        let (bv, dv, ev) = (regs[b], regs[d], regs[e]);
+        if !(0 < dv && dv < LIMIT && -LIMIT < ev && ev < bv && bv < LIMIT) {
+          return false;
+        }
+        let found = (bv % dv == 0 && (ev..bv).contains(&(bv / dv))) as usize;
+        let n = (bv - ev) as usize;
+        add_hits(hits, (s..s + 4).chain(s + 5..s + 9), n);
+        hits[s + 4] += found;
+        writes[g] += 5 * n;
+        writes[e] += n;
+        writes[f] += found;
+        taken[s + 3] += n - found;
+        taken[s + 8] += n - 1;
+        regs[e] = bv;
+        regs[g] = 0;
+        if found > 0 {
+          regs[f] = 0;
+        }
+      },
+      Idiom::IsComposite { b, d, e, f, g } => {
+        let (bv, dv) = (regs[b], regs[d]);
+        if !(0 < dv && dv < bv && 2 < bv && bv < LIMIT) {
+          return false;
+        }
+        // Each d with e running over 2..b, so d * e == b once for every
+        // divisor d with b / d >= 2.
+        let found = divisors_between(bv, dv.max(2), bv / 2);
+        let outer = (bv - dv) as usize;
+        let inner = outer * (bv - 2) as usize;
+        hits[s] += outer;
+        add_hits(hits, (s + 1..s + 5).chain(s + 6..s + 10), inner);
+        hits[s + 5] += found;
+        add_hits(hits, s + 10..s + 14, outer);
+        writes[e] += outer + inner;
+        writes[g] += 5 * inner + 2 * outer;
+        writes[d] += outer;
+        writes[f] += found;
+        taken[s + 4] += inner - found;
+        taken[s + 9] += inner - outer;
+        taken[s + 13] += outer - 1;
+        regs[d] = bv;
+        regs[e] = bv;
+        regs[g] = 0;
+        if found > 0 {
+          regs[f] = 0;
+        }
+      },
+    }
+    true
+  }
+}
+
+impl Program {
+  /// Replaces every loop it recognizes with a superinstruction, returning how
+  /// many it replaced. Programs with jumps by a register are left alone,
+  /// since they could land anywhere inside a loop.
+  pub fn optimize(&mut self) -> usize {
+    let mut jumps = Vec::new();
+    for (pc, code) in self.code.iter().enumerate() {
+      match *code {
+        Code::Jgz(_, Target::To(to)) | Code::Jnz(_, Target::To(to)) | Code::Jmp(to) => {
+          jumps.push((pc, to));
+        },
+        Code::Jgz(_, Target::By(_)) | Code::Jnz(_, Target::By(_)) => return 0,
+        _ => {},
+      }
+    }
+    let mut found = Vec::new();
+    for pc in 0..self.code.len() {
+      let (idiom, len) = match (is_composite_at(&self.code, pc), divides_at(&self.code, pc)) {
+        (Some(idiom), _) => (idiom, 14),
+        (None, Some(idiom)) => (idiom, 9),
+        (None, None) => continue,
+      };
+      let (start, end) = (pc, pc + len);
+      // The loop must only be entered at the top.
+      let enters = |&(from, to): &(usize, i64)| {
+        (start as i64) < to && to < end as i64 && !(start..end).contains(&from)
+      };
+      if jumps.iter().any(enters) {
+        continue;
+      }
+      found.push(Superinstr { idiom, start, end, orig: self.code[pc] });
+    }
+    for superinstr in &found {
+      self.code[superinstr.start] = Code::Idiom(self.superinstrs.len());
+      self.superinstrs.push(*superinstr);
+    }
+    found.len()
+  }
+
+  /// How many loops `optimize` has replaced.
+  pub fn superinstructions(&self) -> usize {
+    self.superinstrs.len()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::cpu::{CPU, Step};
+  use crate::dialect::COPROCESSOR;
+  use crate::instr::Op;
+
+  // Counts the composites among 3..=20 the way the coprocessor does.
+  const COMPOSITES: &str = "\
+set b 3
+set f 1
+set d 2
+set e 2
+set g d
+mul g e
+sub g b
+jnz g 2
+set f 0
+sub e -1
+set g e
+sub g b
+jnz g -8
+sub d -1
+set g d
+sub g b
+jnz g -13
+jnz f 2
+sub h -1
+set g b
+sub g 20
+jnz g 2
+jnz 1 3
+sub b -1
+jnz 1 -23";
+
+  fn run(prog: &Program) -> (i64, Vec<usize>, Counters) {
+    let mut cpu = CPU::new(prog);
+    while cpu.step().unwrap() != Step::Halted {}
+    let counts = [Op::Jnz, Op::Mul, Op::Set, Op::Sub].iter().map(|&op| cpu.count(op)).collect();
+    (cpu.reg("h").unwrap(), counts, cpu.counters().clone())
+  }
+
+  #[test]
+  fn test_optimized_matches_interpreted() {
+    let plain = COPROCESSOR.compile(COMPOSITES).unwrap();
+    let mut fast = COPROCESSOR.compile(COMPOSITES).unwrap();
+    assert_eq!(fast.optimize(), 2);
+    assert_eq!(fast.superinstrs.iter().map(|s| (s.start, s.end)).collect::<Vec<_>>(),
+               vec![(3, 17), (4, 13)]);
+    let res = run(&plain);
+    assert_eq!(res.0, 11);
+    assert_eq!(run(&fast), res);
+  }
+
+  #[test]
+  fn test_jump_into_loop() {
+    let mut prog = COPROCESSOR.compile(&format!("{}\njnz 1 -21", COMPOSITES)).unwrap();
+    assert_eq!(prog.optimize(), 1);
+  }
+
+  #[test]
+  fn test_divisors_between() {
+    assert_eq!(divisors_between(36, 2, 18), 7);
+    assert_eq!(divisors_between(13, 2, 6), 0);
+  }
+}  // mod tests
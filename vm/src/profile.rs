@@ -0,0 +1,197 @@
+//! Where a program spends its time: how often each instruction ran, how
+//! often each register was written and which way every jump went.
+
+use aoc_common::Result;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io;
+use std::result;
+
+use crate::cpu::CPU;
+use crate::disasm::Disassembly;
+
+
+/// The raw counts a `CPU` keeps as it runs.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Counters {
+  pub hits: Vec<usize>,
+  /// By register slot.
+  pub writes: Vec<usize>,
+  /// How often the jump at each pc was taken.
+  pub taken: Vec<usize>,
+  /// Where jumps by a register went, as (from, to).
+  #[serde(serialize_with = "to_triples", deserialize_with = "from_triples")]
+  pub dynamic: BTreeMap<(usize, i64), usize>,
+}
+
+// JSON keys can only be strings, so the jumps are (from, to, count).
+fn to_triples<S>(dynamic: &BTreeMap<(usize, i64), usize>, serializer: S)
+    -> result::Result<S::Ok, S::Error>
+    where S: Serializer {
+  serializer.collect_seq(dynamic.iter().map(|(&(from, to), &n)| (from, to, n)))
+}
+
+fn from_triples<'de, D>(deserializer: D)
+    -> result::Result<BTreeMap<(usize, i64), usize>, D::Error>
+    where D: Deserializer<'de> {
+  let triples = Vec::<(usize, i64, usize)>::deserialize(deserializer)?;
+  Ok(triples.into_iter().map(|(from, to, n)| ((from, to), n)).collect())
+}
+
+impl Counters {
+  pub fn new(len: usize, registers: usize) -> Counters {
+    Counters {
+      hits: vec![0; len],
+      writes: vec![0; registers],
+      taken: vec![0; len],
+      dynamic: BTreeMap::new(),
+    }
+  }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Line {
+  pub pc: usize,
+  pub instr: String,
+  pub hits: usize,
+}
+
+/// How often control went from one pc to another, for every jump.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Edge {
+  pub from: usize,
+  pub to: i64,
+  pub count: usize,
+  /// False for a jump falling through to the next instruction.
+  pub taken: bool,
+}
+
+/// A loop found by the disassembler with the instructions it ran.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct LoopHeat {
+  pub start: usize,
+  pub end: usize,
+  pub depth: usize,
+  pub hits: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Profile {
+  pub total: usize,
+  pub lines: Vec<Line>,
+  pub loops: Vec<LoopHeat>,
+  pub writes: BTreeMap<String, usize>,
+  pub edges: Vec<Edge>,
+}
+
+fn percent(n: usize, total: usize) -> f64 {
+  if total == 0 { 0.0 } else { n as f64 * 100.0 / total as f64 }
+}
+
+impl Profile {
+  pub fn new(cpu: &CPU) -> Profile {
+    let instrs = cpu.program().instrs();
+    let counters = cpu.counters();
+    let lines = instrs.iter()
+                      .zip(&counters.hits)
+                      .enumerate()
+                      .map(|(pc, (instr, &hits))| Line { pc, instr: instr.to_string(), hits })
+                      .collect::<Vec<_>>();
+    let dis = Disassembly::new(instrs);
+    let loops = dis.loops
+                   .iter()
+                   .map(|l| {
+                     let (start, end) = (dis.blocks[l.head].start, dis.blocks[l.tail].end);
+                     let hits = counters.hits[start..end].iter().sum();
+                     LoopHeat { start, end, depth: l.depth, hits }
+                   })
+                   .collect();
+    let registers = cpu.program().registers();
+    let writes = counters.writes
+                         .iter()
+                         .enumerate()
+                         .filter(|&(_, &n)| n > 0)
+                         .map(|(idx, &n)| (registers.name(idx).to_owned(), n))
+                         .collect();
+    let mut edges = Vec::new();
+    for pc in 0..instrs.len() {
+      let (hits, taken) = (counters.hits[pc], counters.taken[pc]);
+      if let Some(to) = dis.target(pc) {
+        if taken > 0 {
+          edges.push(Edge { from: pc, to, count: taken, taken: true });
+        }
+      } else if dis.is_dynamic(pc) {
+        let dynamic = counters.dynamic.range((pc, i64::MIN)..=(pc, i64::MAX));
+        edges.extend(dynamic.map(|(&(from, to), &count)| Edge { from, to, count, taken: true }));
+      } else {
+        continue;
+      }
+      if hits > taken {
+        edges.push(Edge { from: pc, to: pc as i64 + 1, count: hits - taken, taken: false });
+      }
+    }
+    Profile { total: counters.hits.iter().sum(), lines, loops, writes, edges }
+  }
+
+  /// The loops and instructions from hottest to coldest, followed by the
+  /// register writes and jump edges.
+  pub fn report(&self) -> Result<String> {
+    let mut out = String::new();
+    writeln!(out, "{} instructions run", self.total)?;
+    let mut loops = self.loops.clone();
+    loops.sort_by(|x, y| y.hits.cmp(&x.hits).then(x.start.cmp(&y.start)));
+    writeln!(out, "\nLoops")?;
+    for l in &loops {
+      writeln!(out, "{:>14} {:>6.2}%  L{}..L{}, depth {}",
+               l.hits, percent(l.hits, self.total), l.start, l.end - 1, l.depth)?;
+    }
+    let mut lines = self.lines.clone();
+    lines.sort_by(|x, y| y.hits.cmp(&x.hits).then(x.pc.cmp(&y.pc)));
+    writeln!(out, "\nInstructions")?;
+    for line in &lines {
+      writeln!(out, "{:>14} {:>6.2}%  {:>4}: {}",
+               line.hits, percent(line.hits, self.total), line.pc, line.instr)?;
+    }
+    writeln!(out, "\nRegister writes")?;
+    for (name, n) in &self.writes {
+      writeln!(out, "{:>14}  {}", n, name)?;
+    }
+    writeln!(out, "\nJump edges")?;
+    for edge in &self.edges {
+      let how = if edge.taken { "taken" } else { "fallthrough" };
+      writeln!(out, "{:>14}  {} -> {} {}", edge.count, edge.from, edge.to, how)?;
+    }
+    Ok(out)
+  }
+
+  pub fn json(&self) -> Result<String> {
+    Ok(serde_json::to_string_pretty(self).map_err(io::Error::from)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::cpu::Step;
+  use crate::dialect::COPROCESSOR;
+
+  #[test]
+  fn test_profile() {
+    let prog = COPROCESSOR.compile("set a 3\nsub a 1\njnz a -1\nset b 7").unwrap();
+    let mut cpu = CPU::new(&prog);
+    while cpu.step().unwrap() != Step::Halted {}
+    let profile = Profile::new(&cpu);
+    assert_eq!(profile.total, 8);
+    assert_eq!(profile.lines.iter().map(|l| l.hits).collect::<Vec<_>>(), vec![1, 3, 3, 1]);
+    assert_eq!(profile.loops, vec![LoopHeat { start: 1, end: 3, depth: 1, hits: 6 }]);
+    assert_eq!(profile.writes, BTreeMap::from([("a".to_owned(), 4), ("b".to_owned(), 1)]));
+    assert_eq!(profile.edges, vec![Edge { from: 2, to: 1, count: 2, taken: true },
+                                   Edge { from: 2, to: 3, count: 1, taken: false }]);
+    assert!(profile.report().unwrap().contains("\
+Instructions
+             3  37.50%     1: sub a 1
+             3  37.50%     2: jnz a -1
+"));
+  }
+}  // mod tests
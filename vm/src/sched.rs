@@ -0,0 +1,243 @@
+//! Runs several programs side by side, passing what each one sends to the
+//! others, until none of them can get any further.
+
+use aoc_common::{Result, invalid_input};
+use std::fmt;
+
+use crate::bytecode::Program;
+use crate::cpu::{CPU, Step};
+use crate::instr::Op;
+
+
+/// Who receives what a program sends.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Topology {
+  /// Program i sends to program i + 1, and the last to the first.
+  Ring,
+  /// Programs 0 and 1 send to each other, as do 2 and 3 and so on.
+  Pairwise,
+  /// Every program sends to all the others.
+  Broadcast,
+}
+
+/// How long each program runs before the next one gets a turn.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Policy {
+  /// One instruction each.
+  RoundRobin,
+  /// Until it blocks on an empty queue or halts.
+  RunUntilBlock,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+  /// Waiting in a `rcv` with nothing queued.
+  Blocked,
+  Halted,
+}
+
+/// Where a program ended up and what it sent and received on the way.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proc {
+  pub id: usize,
+  pub status: Status,
+  pub pc: i64,
+  /// Messages delivered to it that it never received.
+  pub pending: usize,
+  pub sent: usize,
+  pub received: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report {
+  pub procs: Vec<Proc>,
+}
+
+impl Report {
+  /// Whether any program is left waiting for a message that will never come.
+  pub fn deadlock(&self) -> bool {
+    self.procs.iter().any(|proc| proc.status == Status::Blocked)
+  }
+}
+
+impl fmt::Display for Report {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(f, "{}", if self.deadlock() { "deadlock" } else { "halted" })?;
+    for proc in &self.procs {
+      let status = if proc.status == Status::Blocked { "blocked" } else { "halted" };
+      writeln!(f, "{}: {} at pc {}, {} queued, sent {}, received {}",
+               proc.id, status, proc.pc, proc.pending, proc.sent, proc.received)?;
+    }
+    Ok(())
+  }
+}
+
+#[derive(Debug)]
+pub struct Scheduler<'a> {
+  pub(crate) cpus: Vec<CPU<'a>>,
+  topology: Topology,
+  policy: Policy,
+}
+
+impl<'a> Scheduler<'a> {
+  pub fn new(cpus: Vec<CPU<'a>>, topology: Topology, policy: Policy) -> Result<Scheduler<'a>> {
+    if cpus.is_empty() {
+      return Err(invalid_input!("Nothing to schedule"));
+    }
+    if topology == Topology::Pairwise && !cpus.len().is_multiple_of(2) {
+      return Err(invalid_input!("Pairwise needs an even number of programs, not {}", cpus.len()));
+    }
+    Ok(Scheduler { cpus, topology, policy })
+  }
+
+  /// `n` copies of a program, each with its id in register p as the duet
+  /// puzzle has it, if the program has a register p.
+  pub fn spawn(program: &'a Program, n: usize, topology: Topology, policy: Policy)
+      -> Result<Scheduler<'a>> {
+    let has_id = program.registers().slot("p").is_some();
+    let mut cpus = Vec::new();
+    for id in 0..n {
+      let mut cpu = CPU::new(program);
+      if has_id {
+        cpu.set_reg("p", id as i64)?;
+      }
+      cpus.push(cpu);
+    }
+    Scheduler::new(cpus, topology, policy)
+  }
+
+  pub fn cpus(&self) -> &[CPU<'a>] {
+    &self.cpus
+  }
+
+  /// Has every program record its steps, as `CPU::record` does.
+  pub fn record(&mut self, limit: Option<usize>) {
+    for cpu in &mut self.cpus {
+      cpu.record(limit);
+    }
+  }
+
+  pub(crate) fn recipients(&self, id: usize) -> Vec<usize> {
+    let n = self.cpus.len();
+    match self.topology {
+      Topology::Ring => vec![(id + 1) % n],
+      Topology::Pairwise => vec![id ^ 1],
+      Topology::Broadcast => (0..n).filter(|&to| to != id).collect(),
+    }
+  }
+
+  // Gives one program its turn, returning whether it ran anything.
+  fn turn(&mut self, id: usize) -> Result<bool> {
+    let mut ran = false;
+    loop {
+      match self.cpus[id].step()? {
+        Step::Sent(n) => {
+          for to in self.recipients(id) {
+            self.cpus[to].deliver(n);
+          }
+        },
+        Step::Blocked | Step::Halted => return Ok(ran),
+        Step::Ran | Step::Recovered => {},
+      }
+      ran = true;
+      if self.policy == Policy::RoundRobin {
+        return Ok(true);
+      }
+    }
+  }
+
+  /// Runs every program until they've all halted or are waiting on empty
+  /// queues. Nothing can arrive once a whole round has gone by without any
+  /// program running, so a deadlock is only reported when it's certain.
+  pub fn run(&mut self) -> Result<Report> {
+    loop {
+      let mut ran = false;
+      for id in 0..self.cpus.len() {
+        ran |= self.turn(id)?;
+      }
+      if !ran {
+        break;
+      }
+    }
+    Ok(self.report())
+  }
+
+  pub fn report(&self) -> Report {
+    let procs = self.cpus
+                    .iter()
+                    .enumerate()
+                    .map(|(id, cpu)| Proc {
+                      id,
+                      status: if cpu.instr().is_some() { Status::Blocked } else { Status::Halted },
+                      pc: cpu.pc(),
+                      pending: cpu.pending(),
+                      sent: cpu.tx_count(),
+                      received: cpu.count(Op::Rcv),
+                    })
+                    .collect();
+    Report { procs }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::dialect::DUET;
+
+  fn run(source: &str, n: usize, topology: Topology, policy: Policy) -> Report {
+    let program = DUET.compile(source).unwrap();
+    Scheduler::spawn(&program, n, topology, policy).unwrap().run().unwrap()
+  }
+
+  #[test]
+  fn test_deadlock() {
+    let source = "snd 1\nsnd 2\nsnd p\nrcv a\nrcv b\nrcv c\nrcv d";
+    for policy in [Policy::RoundRobin, Policy::RunUntilBlock] {
+      let report = run(source, 2, Topology::Pairwise, policy);
+      assert!(report.deadlock());
+      assert_eq!(report.to_string(), "\
+deadlock
+0: blocked at pc 6, 0 queued, sent 3, received 3
+1: blocked at pc 6, 0 queued, sent 3, received 3
+");
+    }
+  }
+
+  #[test]
+  fn test_traces_show_the_deadlock() {
+    let program = DUET.compile("snd p\nrcv a\nrcv b").unwrap();
+    let mut sched = Scheduler::spawn(&program, 2, Topology::Pairwise, Policy::RoundRobin).unwrap();
+    sched.record(None);
+    assert!(sched.run().unwrap().deadlock());
+    let traces = sched.cpus().iter().map(|cpu| cpu.trace().unwrap()).collect::<Vec<_>>();
+    let last = traces[0].events.back().unwrap();
+    assert_eq!(last.to_string(), "   1: rcv a  a=1  received 1");
+    assert_eq!(traces[0].state_at(2).unwrap().pc, 2);
+    assert_eq!(traces[0].diff(traces[1]).unwrap().steps.len(), 2);
+  }
+
+  #[test]
+  fn test_ring() {
+    // Program 0 starts a counter that goes once around the ring.
+    let report = run("jgz p 2\nsnd 0\nrcv a\nadd a 1\nsnd a", 3, Topology::Ring,
+                     Policy::RoundRobin);
+    assert!(!report.deadlock());
+    let pending = report.procs.iter().map(|proc| proc.pending).collect::<Vec<_>>();
+    assert_eq!(pending, vec![0, 1, 0]);
+    assert_eq!(report.procs[0].sent, 2);
+  }
+
+  #[test]
+  fn test_broadcast() {
+    let report = run("snd p\nrcv a\nrcv b\nrcv c", 3, Topology::Broadcast,
+                     Policy::RunUntilBlock);
+    let stats = report.procs.iter().map(|proc| (proc.pc, proc.sent, proc.received));
+    assert_eq!(stats.collect::<Vec<_>>(), vec![(3, 1, 2); 3]);
+  }
+
+  #[test]
+  fn test_pairwise_needs_pairs() {
+    let program = DUET.compile("snd p").unwrap();
+    assert!(Scheduler::spawn(&program, 3, Topology::Pairwise, Policy::RoundRobin).is_err());
+  }
+}  // mod tests
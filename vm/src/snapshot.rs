@@ -0,0 +1,118 @@
+//! Everything a `CPU` has done, saved so it can carry on later from the same
+//! place, or be changed first to see what else the program would do.
+
+use aoc_common::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+
+use crate::profile::Counters;
+
+
+/// A `CPU`'s state apart from its program and arithmetic, which it's
+/// restored into.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+  /// The fingerprint of the program it was taken from.
+  pub program: String,
+  pub pc: i64,
+  pub regs: BTreeMap<String, i64>,
+  /// Registers too big for 64 bits, in decimal.
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub big: BTreeMap<String, String>,
+  /// Messages delivered but not yet received.
+  pub inbox: Vec<i64>,
+  pub tx_count: usize,
+  pub(crate) counters: Counters,
+}
+
+impl Snapshot {
+  pub fn json(&self) -> Result<String> {
+    Ok(serde_json::to_string_pretty(self).map_err(io::Error::from)?)
+  }
+
+  pub fn from_json(json: &str) -> Result<Snapshot> {
+    Ok(serde_json::from_str(json).map_err(io::Error::from)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::arith::{Arith, Overflow};
+  use crate::cpu::{CPU, Step};
+  use crate::dialect::DUET;
+  use crate::profile::Profile;
+
+  // Counts b down from 10 + a, adding each to c.
+  const SUM: &str = "set b 10\nadd b a\nadd c b\nadd b -1\njgz b -2";
+
+  fn run(cpu: &mut CPU) {
+    while cpu.step().unwrap() != Step::Halted {}
+  }
+
+  #[test]
+  fn test_resume() {
+    let prog = DUET.compile(SUM).unwrap();
+    let mut whole = CPU::new(&prog);
+    run(&mut whole);
+    let mut first = CPU::new(&prog);
+    for _ in 0..12 {
+      first.step().unwrap();
+    }
+    let json = first.snapshot().json().unwrap();
+    let mut rest = CPU::new(&prog);
+    rest.restore(&Snapshot::from_json(&json).unwrap()).unwrap();
+    run(&mut rest);
+    assert_eq!(rest.reg("c").unwrap(), 55);
+    assert_eq!(rest.snapshot(), whole.snapshot());
+    assert_eq!(Profile::new(&rest), Profile::new(&whole));
+  }
+
+  #[test]
+  fn test_fork() {
+    let prog = DUET.compile(SUM).unwrap();
+    let mut cpu = CPU::new(&prog);
+    cpu.step().unwrap();
+    let mut snapshot = cpu.snapshot();
+    snapshot.regs.insert("a".to_owned(), 5);
+    let mut fork = CPU::new(&prog);
+    fork.restore(&snapshot).unwrap();
+    run(&mut fork);
+    run(&mut cpu);
+    assert_eq!((cpu.reg("c").unwrap(), fork.reg("c").unwrap()), (55, 120));
+  }
+
+  #[test]
+  fn test_messages_and_big_registers() {
+    let prog = DUET.compile("set a 4294967296\nmul a a\nsnd 1\nrcv b\nrcv c").unwrap();
+    let big = Arith { overflow: Overflow::Big, ..Arith::default() };
+    let mut cpu = CPU::with_arith(&prog, big);
+    cpu.deliver(7);
+    cpu.deliver(8);
+    assert_eq!(cpu.run().unwrap(), Step::Sent(1));
+    let snapshot = Snapshot::from_json(&cpu.snapshot().json().unwrap()).unwrap();
+    assert_eq!(snapshot.big["a"], "18446744073709551616");
+    assert_eq!((snapshot.inbox.clone(), snapshot.tx_count), (vec![7, 8], 1));
+    let mut copy = CPU::with_arith(&prog, big);
+    copy.restore(&snapshot).unwrap();
+    assert_eq!(copy.run().unwrap(), Step::Halted);
+    assert_eq!((copy.reg("b").unwrap(), copy.reg("c").unwrap(), copy.tx_count()), (7, 8, 1));
+    assert_eq!(copy.big_reg("a").unwrap(), cpu.big_reg("a").unwrap());
+  }
+
+  #[test]
+  fn test_other_programs_refused() {
+    let prog = DUET.compile(SUM).unwrap();
+    let snapshot = CPU::new(&prog).snapshot();
+    // Even the same length, a program that differs is refused.
+    for other in ["set a 1", "set b 10\nadd b a\nadd c b\nadd b -2\njgz b -2"] {
+      let other = DUET.compile(other).unwrap();
+      let err = CPU::new(&other).restore(&snapshot).unwrap_err();
+      assert_eq!(err.to_string(), "The snapshot is of a different program");
+    }
+    let mut bad = snapshot.clone();
+    bad.big.insert("b".to_owned(), "12x".to_owned());
+    assert_eq!(CPU::new(&prog).restore(&bad).unwrap_err().to_string(), "Invalid number: 12x");
+  }
+}  // mod tests
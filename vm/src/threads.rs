@@ -0,0 +1,179 @@
+//! Runs each of a scheduler's programs on its own thread, with a `rcv` on an
+//! empty queue parking the thread until something arrives.
+
+use aoc_common::{Result, invalid_input};
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+use crate::cpu::{CPU, Step};
+use crate::sched::{Report, Scheduler};
+
+
+#[derive(Debug)]
+struct Shared {
+  queues: Vec<VecDeque<i64>>,
+  parked: Vec<bool>,
+  /// Threads that haven't halted.
+  live: usize,
+  done: bool,
+}
+
+impl Shared {
+  // Every thread still running is parked, and each one that parked had an
+  // empty queue, since a delivery unparks it.
+  fn deadlocked(&self) -> bool {
+    self.parked.iter().filter(|&&p| p).count() == self.live
+  }
+}
+
+#[derive(Debug)]
+struct Mailboxes {
+  shared: Mutex<Shared>,
+  wake: Condvar,
+}
+
+impl Mailboxes {
+  fn send(&self, to: &[usize], n: i64) {
+    let mut shared = self.shared.lock().unwrap();
+    for &id in to {
+      shared.queues[id].push_back(n);
+      shared.parked[id] = false;
+    }
+    self.wake.notify_all();
+  }
+
+  // Hands over everything queued for the program, waiting if there's
+  // nothing yet. Returns false once no more can ever arrive.
+  fn receive(&self, id: usize, cpu: &mut CPU) -> bool {
+    let mut shared = self.shared.lock().unwrap();
+    if shared.queues[id].is_empty() && !shared.done {
+      shared.parked[id] = true;
+      if shared.deadlocked() {
+        shared.done = true;
+        self.wake.notify_all();
+      }
+      while shared.parked[id] && !shared.done {
+        shared = self.wake.wait(shared).unwrap();
+      }
+    }
+    for n in shared.queues[id].drain(..) {
+      cpu.deliver(n);
+    }
+    !shared.done
+  }
+
+  fn halt(&self) {
+    let mut shared = self.shared.lock().unwrap();
+    shared.live -= 1;
+    if shared.live > 0 && shared.deadlocked() {
+      shared.done = true;
+      self.wake.notify_all();
+    }
+  }
+
+  // Stops every thread, so an error in one program doesn't leave the rest
+  // waiting on it.
+  fn abort(&self) {
+    self.shared.lock().unwrap().done = true;
+    self.wake.notify_all();
+  }
+}
+
+fn run_one(id: usize, cpu: &mut CPU, to: &[usize], mailboxes: &Mailboxes) -> Result<()> {
+  loop {
+    match cpu.run() {
+      Ok(Step::Sent(n)) => mailboxes.send(to, n),
+      Ok(Step::Blocked) => {
+        if !mailboxes.receive(id, cpu) {
+          return Ok(());
+        }
+      },
+      Ok(Step::Halted) => {
+        mailboxes.halt();
+        return Ok(());
+      },
+      Ok(_) => {},
+      Err(err) => {
+        mailboxes.abort();
+        return Err(err);
+      },
+    }
+  }
+}
+
+impl<'a> Scheduler<'a> {
+  /// Like `run`, but with every program on a thread of its own. The threads
+  /// stop when all of those still running are parked on empty queues.
+  pub fn run_threads(&mut self) -> Result<Report> {
+    let n = self.cpus.len();
+    let routes = (0..n).map(|id| self.recipients(id)).collect::<Vec<_>>();
+    let mailboxes = Mailboxes {
+      shared: Mutex::new(Shared {
+        queues: vec![VecDeque::new(); n],
+        parked: vec![false; n],
+        live: n,
+        done: false,
+      }),
+      wake: Condvar::new(),
+    };
+    thread::scope(|scope| {
+      let handles = self.cpus
+                        .iter_mut()
+                        .zip(&routes)
+                        .enumerate()
+                        .map(|(id, (cpu, to))| scope.spawn({
+                          let mailboxes = &mailboxes;
+                          move || run_one(id, cpu, to, mailboxes)
+                        }))
+                        .collect::<Vec<_>>();
+      handles.into_iter()
+             .map(|handle| {
+               handle.join().map_err(|_| invalid_input!("A program's thread panicked"))?
+             })
+             .collect::<Result<Vec<_>>>()
+    })?;
+    // Whatever was sent after a program last looked at its queue.
+    let shared = mailboxes.shared.into_inner().unwrap();
+    for (cpu, queue) in self.cpus.iter_mut().zip(shared.queues) {
+      for n in queue {
+        cpu.deliver(n);
+      }
+    }
+    Ok(self.report())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::dialect::DUET;
+  use crate::sched::{Policy, Scheduler, Topology};
+
+  // Program 0 sends a token that every program passes on less one, until
+  // someone receives a zero and halts.
+  const RELAY: &str = "jgz p 2\nsnd 50\nrcv a\njgz a 2\njgz 1 4\nadd a -1\nsnd a\njgz 1 -5";
+
+  #[test]
+  fn test_matches_scheduler() {
+    let program = DUET.compile(RELAY).unwrap();
+    // With one sender per queue the order of every queue is fixed, so the
+    // threads must end up exactly where the scheduler does.
+    for topology in [Topology::Ring, Topology::Pairwise] {
+      let mut sched = Scheduler::spawn(&program, 4, topology, Policy::RoundRobin).unwrap();
+      let expected = sched.run().unwrap();
+      let mut threads = Scheduler::spawn(&program, 4, topology, Policy::RoundRobin).unwrap();
+      let report = threads.run_threads().unwrap();
+      assert!(report.deadlock());
+      assert_eq!(report, expected);
+    }
+  }
+
+  #[test]
+  fn test_halts() {
+    let program = DUET.compile("snd p\nrcv a").unwrap();
+    let mut sched = Scheduler::spawn(&program, 3, Topology::Broadcast, Policy::RoundRobin).unwrap();
+    let report = sched.run_threads().unwrap();
+    assert!(!report.deadlock());
+    assert_eq!(report.procs.iter().map(|proc| proc.pending).sum::<usize>(), 3);
+  }
+}  // mod tests
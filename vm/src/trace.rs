@@ -0,0 +1,523 @@
+//! A record of what a `CPU` did, step by step: each instruction it ran, the
+//! registers that changed and anything sent or received. A trace can keep
+//! just its last so many steps, be saved as JSON lines or in a compact
+//! binary form, be replayed to any step it covers, and be compared with
+//! another.
+
+use aoc_common::{Result, invalid_input};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::io;
+
+
+const MAGIC: &[u8] = b"VMTRACE2";
+
+/// Where a program was and what its registers held.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct State {
+  pub step: usize,
+  pub pc: i64,
+  pub regs: BTreeMap<String, i64>,
+  /// Registers too big for 64 bits, in decimal.
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub big: BTreeMap<String, String>,
+}
+
+impl State {
+  fn apply(&mut self, event: &Event) {
+    for (name, n) in &event.writes {
+      self.big.remove(name);
+      self.regs.insert(name.clone(), *n);
+    }
+    for (name, n) in &event.big_writes {
+      self.regs.remove(name);
+      self.big.insert(name.clone(), n.clone());
+    }
+    self.pc = event.next;
+    self.step = event.step + 1;
+  }
+
+  /// Every register's value in decimal, whatever its size.
+  pub fn values(&self) -> BTreeMap<&str, String> {
+    let small = self.regs.iter().map(|(name, n)| (name.as_str(), n.to_string()));
+    small.chain(self.big.iter().map(|(name, n)| (name.as_str(), n.clone()))).collect()
+  }
+}
+
+impl fmt::Display for State {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "step {} pc={}", self.step, self.pc)?;
+    for (name, n) in self.values() {
+      write!(f, " {}={}", name, n)?;
+    }
+    Ok(())
+  }
+}
+
+/// One instruction run.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+  pub step: usize,
+  pub pc: usize,
+  pub instr: String,
+  /// The registers it changed, with their new values.
+  pub writes: Vec<(String, i64)>,
+  /// The same for values too big for 64 bits, in decimal.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub big_writes: Vec<(String, String)>,
+  pub sent: Option<i64>,
+  pub received: Option<i64>,
+  /// The pc after it.
+  pub next: i64,
+}
+
+impl fmt::Display for Event {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:>4}: {}", self.pc, self.instr)?;
+    for (name, n) in &self.writes {
+      write!(f, "  {}={}", name, n)?;
+    }
+    for (name, n) in &self.big_writes {
+      write!(f, "  {}={}", name, n)?;
+    }
+    if let Some(n) = self.sent {
+      write!(f, "  sent {}", n)?;
+    }
+    if let Some(n) = self.received {
+      write!(f, "  received {}", n)?;
+    }
+    if self.next != self.pc as i64 + 1 {
+      write!(f, "  -> {}", self.next)?;
+    }
+    Ok(())
+  }
+}
+
+// What starts a JSON lines trace, before the events.
+#[derive(Serialize, Deserialize)]
+struct Header {
+  start: State,
+  limit: Option<usize>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trace {
+  /// The state before the first event kept.
+  pub start: State,
+  pub events: VecDeque<Event>,
+  /// How many events to keep, dropping the oldest; all of them when None.
+  pub limit: Option<usize>,
+}
+
+/// Where two traces differ.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Diff {
+  /// The first step both traces cover.
+  pub from: usize,
+  /// Registers that differ at that step, with the value in each trace.
+  pub regs: Vec<(String, Option<String>, Option<String>)>,
+  /// Every later step that differs, with what each trace did, if anything.
+  pub steps: Vec<(usize, Option<Event>, Option<Event>)>,
+}
+
+impl Diff {
+  pub fn is_empty(&self) -> bool {
+    self.regs.is_empty() && self.steps.is_empty()
+  }
+}
+
+fn show<T: fmt::Display>(x: &Option<T>, none: &str) -> String {
+  x.as_ref().map_or(none.to_owned(), |x| x.to_string())
+}
+
+impl fmt::Display for Diff {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (name, x, y) in &self.regs {
+      writeln!(f, "at step {}: {} = {} | {}", self.from, name, show(x, "-"), show(y, "-"))?;
+    }
+    for (step, x, y) in &self.steps {
+      writeln!(f, "step {}", step)?;
+      writeln!(f, "  < {}", show(x, "(ended)"))?;
+      writeln!(f, "  > {}", show(y, "(ended)"))?;
+    }
+    Ok(())
+  }
+}
+
+// Unsigned LEB128.
+fn put(out: &mut Vec<u8>, mut n: u64) {
+  while n >= 0x80 {
+    out.push(n as u8 | 0x80);
+    n >>= 7;
+  }
+  out.push(n as u8);
+}
+
+// Zigzag, so small negative numbers stay small.
+fn put_int(out: &mut Vec<u8>, n: i64) {
+  put(out, ((n << 1) ^ (n >> 63)) as u64);
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) {
+  put(out, s.len() as u64);
+  out.extend_from_slice(s.as_bytes());
+}
+
+fn put_opt(out: &mut Vec<u8>, n: Option<i64>) {
+  match n {
+    Some(n) => {
+      out.push(1);
+      put_int(out, n);
+    },
+    None => out.push(0),
+  }
+}
+
+struct Reader<'a> {
+  data: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> Reader<'a> {
+  fn byte(&mut self) -> Result<u8> {
+    let b = *self.data.get(self.pos).ok_or_else(|| invalid_input!("Trace cut short"))?;
+    self.pos += 1;
+    Ok(b)
+  }
+
+  fn uint(&mut self) -> Result<u64> {
+    let mut n = 0u64;
+    for shift in (0..64).step_by(7) {
+      let b = self.byte()?;
+      n |= ((b & 0x7f) as u64) << shift;
+      if b < 0x80 {
+        return Ok(n);
+      }
+    }
+    Err(invalid_input!("Number too long in trace"))
+  }
+
+  fn size(&mut self) -> Result<usize> {
+    Ok(self.uint()? as usize)
+  }
+
+  fn int(&mut self) -> Result<i64> {
+    let n = self.uint()?;
+    Ok((n >> 1) as i64 ^ -((n & 1) as i64))
+  }
+
+  fn str(&mut self) -> Result<String> {
+    let len = self.size()?;
+    let end = self.pos.checked_add(len).ok_or_else(|| invalid_input!("Trace cut short"))?;
+    let bytes = self.data.get(self.pos..end).ok_or_else(|| invalid_input!("Trace cut short"))?;
+    self.pos = end;
+    String::from_utf8(bytes.to_vec()).map_err(|_| invalid_input!("Invalid text in trace"))
+  }
+
+  // A register, by its place in the start state.
+  fn name(&mut self, names: &[String]) -> Result<String> {
+    names.get(self.size()?).cloned().ok_or_else(|| invalid_input!("Unknown register"))
+  }
+
+  fn opt(&mut self) -> Result<Option<i64>> {
+    Ok(if self.byte()? == 0 { None } else { Some(self.int()?) })
+  }
+}
+
+// The step after `len` events from `start`, which `Trace::end` relies on
+// fitting.
+fn end_of(start: usize, len: usize) -> Result<usize> {
+  start.checked_add(len).ok_or_else(|| invalid_input!("Too many steps in trace"))
+}
+
+impl Trace {
+  pub fn new(start: State, limit: Option<usize>) -> Trace {
+    Trace { start, events: VecDeque::new(), limit }
+  }
+
+  /// The step after the last event.
+  pub fn end(&self) -> usize {
+    self.start.step + self.events.len()
+  }
+
+  pub(crate) fn push(&mut self, event: Event) {
+    if self.limit == Some(self.events.len()) {
+      match self.events.pop_front() {
+        Some(old) => self.start.apply(&old),
+        None => return self.start.apply(&event),
+      }
+    }
+    self.events.push_back(event);
+  }
+
+  pub fn event(&self, step: usize) -> Option<&Event> {
+    self.events.get(step.checked_sub(self.start.step)?)
+  }
+
+  /// The state before the given step runs, for any step the trace covers.
+  pub fn state_at(&self, step: usize) -> Result<State> {
+    if step < self.start.step || step > self.end() {
+      return Err(invalid_input!("Step {} is outside the trace, which covers {} to {}",
+                                step, self.start.step, self.end()));
+    }
+    let mut state = self.start.clone();
+    for event in self.events.iter().take(step - self.start.step) {
+      state.apply(event);
+    }
+    Ok(state)
+  }
+
+  /// Compares two traces from the first step both cover.
+  pub fn diff(&self, other: &Trace) -> Result<Diff> {
+    let from = self.start.step.max(other.start.step);
+    let (x, y) = (self.state_at(from)?, other.state_at(from)?);
+    let (x, y) = (x.values(), y.values());
+    let mut names = x.keys().chain(y.keys()).collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    let regs = names.into_iter()
+                    .map(|&name| (name.to_owned(), x.get(name).cloned(), y.get(name).cloned()))
+                    .filter(|(_, x, y)| x != y)
+                    .collect();
+    let steps = (from..self.end().max(other.end()))
+        .map(|step| (step, self.event(step).cloned(), other.event(step).cloned()))
+        .filter(|(_, x, y)| x != y)
+        .collect();
+    Ok(Diff { from, regs, steps })
+  }
+
+  pub fn to_jsonl(&self) -> Result<String> {
+    let header = Header { start: self.start.clone(), limit: self.limit };
+    let mut out = serde_json::to_string(&header).map_err(io::Error::from)?;
+    out.push('\n');
+    for event in &self.events {
+      out += &serde_json::to_string(event).map_err(io::Error::from)?;
+      out.push('\n');
+    }
+    Ok(out)
+  }
+
+  /// The compact form, with registers named by their place in the start
+  /// state and every number a varint, apart from big ones in decimal.
+  pub fn to_bytes(&self) -> Result<Vec<u8>> {
+    let names = self.start.regs.keys().chain(self.start.big.keys()).collect::<Vec<_>>();
+    let slot = |name: &String| {
+      names.iter().position(|&known| known == name)
+           .ok_or_else(|| invalid_input!("Unknown register in trace: {}", name))
+    };
+    let mut out = MAGIC.to_vec();
+    put(&mut out, self.start.step as u64);
+    put_int(&mut out, self.start.pc);
+    put(&mut out, self.start.regs.len() as u64);
+    for (name, &n) in &self.start.regs {
+      put_str(&mut out, name);
+      put_int(&mut out, n);
+    }
+    put(&mut out, self.start.big.len() as u64);
+    for (name, n) in &self.start.big {
+      put_str(&mut out, name);
+      put_str(&mut out, n);
+    }
+    put(&mut out, self.limit.map_or(0, |n| n as u64 + 1));
+    put(&mut out, self.events.len() as u64);
+    for event in &self.events {
+      put(&mut out, event.pc as u64);
+      put_str(&mut out, &event.instr);
+      put(&mut out, event.writes.len() as u64);
+      for (name, n) in &event.writes {
+        put(&mut out, slot(name)? as u64);
+        put_int(&mut out, *n);
+      }
+      put(&mut out, event.big_writes.len() as u64);
+      for (name, n) in &event.big_writes {
+        put(&mut out, slot(name)? as u64);
+        put_str(&mut out, n);
+      }
+      put_opt(&mut out, event.sent);
+      put_opt(&mut out, event.received);
+      put_int(&mut out, event.next);
+    }
+    Ok(out)
+  }
+
+  /// Reads a trace in either form.
+  pub fn load(data: &[u8]) -> Result<Trace> {
+    match data.strip_prefix(MAGIC) {
+      Some(data) => Trace::from_bytes(data),
+      None => Trace::from_jsonl(data),
+    }
+  }
+
+  fn from_jsonl(data: &[u8]) -> Result<Trace> {
+    let text = std::str::from_utf8(data).map_err(|_| invalid_input!("Not a trace"))?;
+    let mut lines = text.lines().enumerate();
+    let json = |(idx, line): (usize, &str)| {
+      invalid_input!("line {}: Not a trace: {}", idx + 1, line)
+    };
+    let header = lines.next().ok_or_else(|| invalid_input!("Empty trace"))?;
+    let Header { start, limit } = serde_json::from_str(header.1).map_err(|_| json(header))?;
+    let mut trace = Trace::new(start, limit);
+    for line in lines {
+      trace.events.push_back(serde_json::from_str(line.1).map_err(|_| json(line))?);
+    }
+    end_of(trace.start.step, trace.events.len())?;
+    Ok(trace)
+  }
+
+  fn from_bytes(data: &[u8]) -> Result<Trace> {
+    let mut r = Reader { data, pos: 0 };
+    let (step, pc) = (r.size()?, r.int()?);
+    let mut names = Vec::new();
+    let (mut regs, mut big) = (BTreeMap::new(), BTreeMap::new());
+    for _ in 0..r.size()? {
+      let name = r.str()?;
+      regs.insert(name.clone(), r.int()?);
+      names.push(name);
+    }
+    for _ in 0..r.size()? {
+      let name = r.str()?;
+      big.insert(name.clone(), r.str()?);
+      names.push(name);
+    }
+    let limit = r.size()?.checked_sub(1);
+    let mut trace = Trace::new(State { step, pc, regs, big }, limit);
+    for step in step..end_of(step, r.size()?)? {
+      let (pc, instr) = (r.size()?, r.str()?);
+      let mut writes = Vec::new();
+      for _ in 0..r.size()? {
+        writes.push((r.name(&names)?, r.int()?));
+      }
+      let mut big_writes = Vec::new();
+      for _ in 0..r.size()? {
+        big_writes.push((r.name(&names)?, r.str()?));
+      }
+      let (sent, received, next) = (r.opt()?, r.opt()?, r.int()?);
+      trace.events.push_back(Event { step, pc, instr, writes, big_writes, sent, received, next });
+    }
+    Ok(trace)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::arith::{Arith, Overflow};
+  use crate::cpu::{CPU, Step};
+  use crate::dialect::DUET;
+
+  // Sends a countdown from p + 2, then waits for an answer.
+  const COUNTDOWN: &str = "set a p\nadd a 2\nsnd a\nadd a -1\njgz a -2\nrcv b";
+
+  fn record(p: i64, limit: Option<usize>) -> Trace {
+    let prog = DUET.compile(COUNTDOWN).unwrap();
+    let mut cpu = CPU::new(&prog);
+    cpu.set_reg("p", p).unwrap();
+    cpu.record(limit);
+    cpu.deliver(-7);
+    while cpu.step().unwrap() != Step::Halted {}
+    cpu.take_trace().unwrap()
+  }
+
+  #[test]
+  fn test_replay() {
+    let trace = record(1, None);
+    assert_eq!(trace.end(), 12);
+    assert_eq!(trace.event(2).unwrap().to_string(), "   2: snd a  sent 3");
+    assert_eq!(trace.event(4).unwrap().to_string(), "   4: jgz a -2  -> 2");
+    assert_eq!(trace.event(11).unwrap().to_string(), "   5: rcv b  b=-7  received -7");
+    let state = trace.state_at(5).unwrap();
+    assert_eq!((state.pc, state.regs["a"], state.regs["p"]), (2, 2, 1));
+    assert!(trace.state_at(12).unwrap().to_string().starts_with("step 12 pc=6 a=0 b=-7 c=0 "));
+    assert!(trace.state_at(13).is_err());
+  }
+
+  #[test]
+  fn test_ring_buffer() {
+    let full = record(1, None);
+    let last = record(1, Some(4));
+    assert_eq!(last.events.len(), 4);
+    assert_eq!(last.start, full.state_at(8).unwrap());
+    assert_eq!(last.state_at(10).unwrap(), full.state_at(10).unwrap());
+    assert_eq!(last.state_at(7).unwrap_err().to_string(),
+               "Step 7 is outside the trace, which covers 8 to 12");
+  }
+
+  #[test]
+  fn test_round_trips() {
+    for trace in [record(0, None), record(3, Some(5))] {
+      assert_eq!(Trace::load(&trace.to_bytes().unwrap()).unwrap(), trace);
+      assert_eq!(Trace::load(trace.to_jsonl().unwrap().as_bytes()).unwrap(), trace);
+    }
+    assert!(Trace::load(&MAGIC[..6]).is_err());
+    assert!(Trace::load(&record(0, None).to_bytes().unwrap()[..20]).is_err());
+  }
+
+  #[test]
+  fn test_bad_bytes() {
+    let bytes = record(1, None).to_bytes().unwrap();
+    for len in 0..bytes.len() {
+      assert!(Trace::load(&bytes[..len]).is_err());
+    }
+    // Wherever it's corrupted, loading it mustn't panic.
+    for idx in MAGIC.len()..bytes.len() {
+      let mut bad = bytes.clone();
+      bad[idx] = 0xff;
+      let _ = Trace::load(&bad);
+    }
+    // A register name as long as can be.
+    let mut bad = MAGIC.to_vec();
+    for n in [0, 0, 1, u64::MAX] {
+      put(&mut bad, n);
+    }
+    assert_eq!(Trace::load(&bad).unwrap_err().to_string(), "Trace cut short");
+    // A first step so late the ones after it can't be numbered.
+    let mut bad = MAGIC.to_vec();
+    for n in [u64::MAX, 0, 0, 0, 0, 1] {
+      put(&mut bad, n);
+    }
+    assert_eq!(Trace::load(&bad).unwrap_err().to_string(), "Too many steps in trace");
+    let start = State { step: usize::MAX, pc: 0, regs: BTreeMap::new(), big: BTreeMap::new() };
+    let mut far = Trace::new(start, None);
+    far.events.push_back(record(0, None).event(0).unwrap().clone());
+    let jsonl = far.to_jsonl().unwrap();
+    assert_eq!(Trace::load(jsonl.as_bytes()).unwrap_err().to_string(), "Too many steps in trace");
+  }
+
+  #[test]
+  fn test_big_registers() {
+    let prog = DUET.compile("set a 4294967296\nmul a a\nadd b a\nmul a 0").unwrap();
+    let mut cpu = CPU::with_arith(&prog, Arith { overflow: Overflow::Big, ..Arith::default() });
+    cpu.record(None);
+    cpu.step().unwrap();
+    assert_eq!(cpu.step().unwrap(), Step::Ran);
+    let first = cpu.take_trace().unwrap();
+    assert_eq!(first.event(1).unwrap().to_string(), "   1: mul a a  a=18446744073709551616");
+    // Recording can start with a register already big.
+    cpu.record(None);
+    assert_eq!(cpu.run().unwrap(), Step::Halted);
+    let rest = cpu.take_trace().unwrap();
+    assert_eq!(rest.start.big["a"], "18446744073709551616");
+    let end = rest.state_at(rest.end()).unwrap();
+    assert_eq!((end.regs["a"], end.big["b"].as_str()), (0, "18446744073709551616"));
+    assert!(!end.big.contains_key("a"));
+    assert_eq!(Trace::load(&rest.to_bytes().unwrap()).unwrap(), rest);
+    assert_eq!(Trace::load(rest.to_jsonl().unwrap().as_bytes()).unwrap(), rest);
+  }
+
+  #[test]
+  fn test_diff() {
+    assert!(record(0, None).diff(&record(0, None)).unwrap().is_empty());
+    let diff = record(0, None).diff(&record(1, None)).unwrap();
+    assert_eq!(diff.regs, vec![("p".to_owned(), Some("0".to_owned()), Some("1".to_owned()))]);
+    assert_eq!(diff.to_string().lines().take(7).collect::<Vec<_>>(), vec![
+      "at step 0: p = 0 | 1",
+      "step 0",
+      "  <    0: set a p",
+      "  >    0: set a p  a=1",
+      "step 1",
+      "  <    1: add a 2  a=2",
+      "  >    1: add a 2  a=3",
+    ]);
+  }
+}  // mod tests
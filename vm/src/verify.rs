@@ -0,0 +1,177 @@
+//! Checks a program's text before anything runs it, collecting every problem
+//! rather than stopping at the first.
+
+use aoc_common::{AocError, Result, column_of, invalid_input, to_i64};
+use std::result;
+
+use crate::dialect::{Dialect, is_ident};
+use crate::instr::{Instr, Op, Val, jump_target};
+
+
+fn arity(op: Op) -> usize {
+  match op {
+    Op::Rcv | Op::Recover | Op::Snd => 1,
+    _ => 2,
+  }
+}
+
+// Whether the op's first operand is the register it writes.
+fn writes(op: Op) -> bool {
+  matches!(op, Op::Add | Op::Mod | Op::Mul | Op::Rcv | Op::Set | Op::Sub)
+}
+
+// How far a jump with a constant offset goes, unless it's never taken.
+fn jump_offset(instr: &Instr) -> Option<i64> {
+  let (cond, offset, taken): (_, _, fn(i64) -> bool) = match *instr {
+    Instr::Jgz(ref x, ref y) => (x, y, |n| n > 0),
+    Instr::Jnz(ref x, ref y) => (x, y, |n| n != 0),
+    _ => return None,
+  };
+  match (cond, offset) {
+    (&Val::Num(n), _) if !taken(n) => None,
+    (_, &Val::Num(n)) => Some(n),
+    _ => None,
+  }
+}
+
+// The problems found in a program as a single error.
+pub(crate) fn combine<T>(value: T, mut problems: Vec<AocError>) -> Result<T> {
+  match problems.len() {
+    0 => Ok(value),
+    1 => Err(problems.remove(0)),
+    n => {
+      let lines = problems.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+      Err(invalid_input!("{} problems in the program:\n{}", n, lines.join("\n")))
+    },
+  }
+}
+
+// How a jump target outside the program is reported.
+pub(crate) fn check_jump(pc: usize, len: usize, instr: &Instr) -> Option<String> {
+  let offset = jump_offset(instr)?;
+  match jump_target(pc, offset) {
+    to if (0..=len as i64).contains(&to) => None,
+    // Where it stops when the jump is too far to add up.
+    i64::MAX => Some(format!("Jumps by {}, outside the program", offset)),
+    to => Some(format!("Jumps to {}, outside the program", to)),
+  }
+}
+
+impl Dialect {
+  // Parses line `line_no`, or lists everything wrong with it.
+  pub(crate) fn check(&self, line_no: usize, line: &str) -> result::Result<Instr, Vec<AocError>> {
+    let fields = line.split_whitespace().collect::<Vec<_>>();
+    self.check_fields(line_no, line, &fields, &|_, _| None)
+  }
+
+  // Builds an instruction from the fields of `line`. Operands that aren't
+  // numbers are looked up with `resolve`, which gets the operand's index as
+  // well as its text, before they're taken as registers.
+  pub(crate) fn check_fields(&self, line_no: usize, line: &str, fields: &[&str],
+                             resolve: &dyn Fn(usize, &str) -> Option<i64>)
+      -> result::Result<Instr, Vec<AocError>> {
+    let problem = |field: &str, msg: String| {
+      AocError::parse(line_no, column_of(line, field), line, &msg)
+    };
+    let (&mnemonic, operands) = match fields.split_first() {
+      Some(split) => split,
+      None => return Err(vec![AocError::parse(line_no, 1, line, "Missing instruction")]),
+    };
+    let op = match self.op(&mnemonic.to_lowercase()) {
+      Some(op) => op,
+      None => return Err(vec![problem(mnemonic, format!("Unknown instruction: {}", mnemonic))]),
+    };
+    let mut problems = Vec::new();
+    if operands.len() != arity(op) {
+      let plural = if arity(op) == 1 { "" } else { "s" };
+      problems.push(problem(mnemonic, format!("{} takes {} operand{}, not {}",
+                                              op.name(), arity(op), plural, operands.len())));
+    }
+    let mut vals = Vec::new();
+    for (idx, &field) in operands.iter().enumerate() {
+      if let Some(n) = to_i64(field).ok().or_else(|| resolve(idx, field)) {
+        vals.push(Val::Num(n));
+      } else if self.valid_reg(field) {
+        vals.push(Val::Reg(field.to_owned()));
+      } else if is_ident(field) {
+        problems.push(problem(field, format!("Unknown register: {}", field)));
+      } else {
+        problems.push(problem(field, format!("Invalid operand: {}", field)));
+      }
+    }
+    if let (true, Some(&field), Some(Val::Num(_))) = (writes(op), operands.first(), vals.first()) {
+      problems.push(problem(field, format!("Writes to an immediate: {}", field)));
+    }
+    if !problems.is_empty() {
+      return Err(problems);
+    }
+    let mut vals = vals.into_iter();
+    let x = vals.next().unwrap_or(Val::None);
+    Ok(op.instr(x, vals.next().unwrap_or(Val::None)))
+  }
+
+  /// Parses every line of a program, returning the instructions of those
+  /// that parsed and every problem found. Besides each line's own problems,
+  /// constant jumps must stay within the program, though one just past the
+  /// end is how a program halts.
+  pub fn verify(&self, data: &str) -> (Vec<Instr>, Vec<AocError>) {
+    let lines = data.lines().collect::<Vec<_>>();
+    let mut instrs = Vec::new();
+    let mut problems = Vec::new();
+    for (pc, line) in lines.iter().enumerate() {
+      match self.check(pc + 1, line) {
+        Ok(instr) => {
+          if let Some(msg) = check_jump(pc, lines.len(), &instr) {
+            let offset = line.split_whitespace().last().unwrap_or(line);
+            problems.push(AocError::parse(pc + 1, column_of(line, offset), line, &msg));
+          }
+          instrs.push(instr);
+        },
+        Err(errs) => problems.extend(errs),
+      }
+    }
+    (instrs, problems)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::dialect::{COPROCESSOR, DUET};
+
+  fn problems(source: &str) -> Vec<String> {
+    DUET.verify(source).1.iter().map(|err| err.to_string()).collect()
+  }
+
+  #[test]
+  fn test_every_problem_reported() {
+    assert_eq!(problems("set a\nsnd a b\nadd 2 a\nmul a 1x\njgz a -5\nrcv a"), vec![
+      "line 1, col 1: set takes 2 operands, not 1 (in 'set a')",
+      "line 2, col 1: snd takes 1 operand, not 2 (in 'snd a b')",
+      "line 3, col 5: Writes to an immediate: 2 (in 'add 2 a')",
+      "line 4, col 7: Invalid operand: 1x (in 'mul a 1x')",
+      "line 5, col 7: Jumps to -1, outside the program (in 'jgz a -5')",
+    ]);
+    assert_eq!(COPROCESSOR.verify("set q 1").1[0].to_string(),
+               "line 1, col 5: Unknown register: q (in 'set q 1')");
+  }
+
+  #[test]
+  fn test_halting_jumps_allowed() {
+    assert!(problems("jgz a 2\nsnd a").is_empty());
+    assert!(problems("jgz 0 -9\nsnd a").is_empty());
+    assert_eq!(problems("snd a\njgz 1 2").len(), 1);
+    assert_eq!(problems("snd a\njgz a 9223372036854775807"), vec![
+      "line 2, col 7: Jumps by 9223372036854775807, outside the program \
+       (in 'jgz a 9223372036854775807')",
+    ]);
+  }
+
+  #[test]
+  fn test_parse_reports_all() {
+    let err = DUET.parse("set a\nsnd 1\nadd 2 a").unwrap_err();
+    assert_eq!(err.to_string(), "\
+2 problems in the program:
+line 1, col 1: set takes 2 operands, not 1 (in 'set a')
+line 3, col 5: Writes to an immediate: 2 (in 'add 2 a')");
+  }
+}  // mod tests